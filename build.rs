@@ -27,6 +27,11 @@ const COMPONENT_SPEC: &'static str = "components.toml";
 const SPATIAL_HASH_SPEC: &'static str = "spatial_hash.toml";
 const TILE_SHEET_IMAGE: &'static str = "tiles.png";
 const TILE_SHEET_SPEC: &'static str = "tiles.toml";
+const VIEWPORT_SPEC: &'static str = "viewport.toml";
+const BEHAVIOUR_SPEC: &'static str = "behaviours.toml";
+const VEIL_SPEC: &'static str = "veils.toml";
+const VEIL_HAZARD_SPEC: &'static str = "veil_hazards.toml";
+const PROJECTILE_SPEC: &'static str = "projectiles.toml";
 
 const ENTITY_STORE_MACROS: &'static str = "src/entity_store/macros.gen.rs";
 const ENTITY_STORE_TEMPLATE: &'static str = "src/entity_store/macros.hbs.rs";
@@ -294,6 +299,16 @@ fn copy_resource_dir() {
             .expect("Failed to copy tile sheet spec");
         fs::copy(stage_path(TILE_SHEET_IMAGE), &dest_resource_path.join(resources::TILE_SHEET_IMAGE))
             .expect("Failed to copy tile sheet image");
+        fs::copy(res_src_path(VIEWPORT_SPEC), &dest_resource_path.join(resources::VIEWPORT_SPEC))
+            .expect("Failed to copy viewport spec");
+        fs::copy(res_src_path(BEHAVIOUR_SPEC), &dest_resource_path.join(resources::BEHAVIOUR_SPEC))
+            .expect("Failed to copy behaviour spec");
+        fs::copy(res_src_path(VEIL_SPEC), &dest_resource_path.join(resources::VEIL_SPEC))
+            .expect("Failed to copy veil spec");
+        fs::copy(res_src_path(VEIL_HAZARD_SPEC), &dest_resource_path.join(resources::VEIL_HAZARD_SPEC))
+            .expect("Failed to copy veil hazard spec");
+        fs::copy(res_src_path(PROJECTILE_SPEC), &dest_resource_path.join(resources::PROJECTILE_SPEC))
+            .expect("Failed to copy projectile spec");
     }
 }
 
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use entity_store::*;
+use spatial_hash::SpatialHashTable;
+use knowledge::PlayerKnowledgeGrid;
+use grid_search::{self, SearchEnv};
+use direction::DirectionsCardinal;
+use content::ALERT_LOUDNESS;
+use noise;
+
+// If the given npc can see the player, tells each of its allies within
+// earshot or in view where the player is.
+pub fn broadcast(id: EntityId,
+                 entity_store: &EntityStore,
+                 spatial_hash: &SpatialHashTable,
+                 search_env: &mut SearchEnv,
+                 knowledge: &mut HashMap<EntityId, PlayerKnowledgeGrid>,
+                 time: u64) -> grid_search::Result<()> {
+
+    let source = if let Some(position) = entity_store.position.get(&id) {
+        *position
+    } else {
+        return Ok(());
+    };
+
+    let mut allies = Vec::new();
+
+    let player_coord = {
+        let sender_knowledge = if let Some(k) = knowledge.get(&id) {
+            k
+        } else {
+            return Ok(());
+        };
+
+        let player_coord = match sender_knowledge.player_coord() {
+            Some(coord) if sender_knowledge.is_visible(coord, time) => coord,
+            _ => return Ok(()),
+        };
+
+        for ally_id in entity_store.npc.iter() {
+            if let Some(position) = entity_store.position.get(ally_id) {
+                if sender_knowledge.is_visible(*position, time) {
+                    allies.push(*ally_id);
+                }
+            }
+        }
+
+        player_coord
+    };
+
+    grid_search::spread(search_env, spatial_hash, source, DirectionsCardinal, ALERT_LOUDNESS, noise::attenuation,
+                        |coord, _| {
+        if let Some(cell) = spatial_hash.get(coord) {
+            allies.extend(cell.npc_set.iter().cloned());
+        }
+    })?;
+
+    for ally_id in allies {
+        if ally_id == id {
+            continue;
+        }
+        if let Some(ally_knowledge) = knowledge.get_mut(&ally_id) {
+            ally_knowledge.alert(player_coord);
+        }
+    }
+
+    Ok(())
+}
@@ -1,12 +1,14 @@
-use behaviour::{BehaviourEnv, BehaviourState};
-use knowledge::{PlayerKnowledgeGrid, PlayerKnowledgeCell};
+use behaviour::{BehaviourEnv, BehaviourState, AlertState, movement_cost};
+use knowledge::PlayerKnowledgeGrid;
 use content::ActionType;
 use direction::DirectionsCardinal;
 use entity_store::{EntityId, EntityStore};
-use grid_search::bfs_coord;
+use spatial_hash::SpatialHashTable;
+use grid_search::astar;
 
 pub fn attack(id: EntityId,
               entity_store: &EntityStore,
+              spatial_hash: &SpatialHashTable,
               knowledge: &PlayerKnowledgeGrid,
               env: &mut BehaviourEnv,
               state: &mut BehaviourState) -> Option<ActionType> {
@@ -19,12 +21,25 @@ pub fn attack(id: EntityId,
         return None;
     };
 
-    let can_enter = |cell: &PlayerKnowledgeCell| {
-        return !cell.solid || cell.door.is_some();
-    };
+    state.alert = AlertState::Chasing;
+
+    let player_coord = entity_store.player.iter().next()
+        .and_then(|player_id| entity_store.position.get(player_id));
+
+    let key_ring = entity_store.key_ring.get(&id);
+    let has_keys = key_ring.map(|k| !k.is_empty()).unwrap_or(false);
+
+    // npcs that know where the player is share a flow field, unless
+    // they have keys which might open a shorter route
+    if Some(&dest) == player_coord && !has_keys {
+        if let Some(direction) = env.flow_field.next_direction(position, dest, spatial_hash) {
+            state.prev_step = None;
+            return Some(ActionType::Walk(id, direction));
+        }
+    }
 
-    if let Err(_) = bfs_coord(&mut env.search_env, knowledge, position, DirectionsCardinal,
-                              dest, can_enter, &mut state.path) {
+    if let Err(_) = astar(&mut env.search_env, knowledge, position, DirectionsCardinal,
+                          dest, |cell| movement_cost(cell, key_ring), &mut state.path) {
         return None;
     }
 
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use content::BehaviourType;
+use resources::{self, BEHAVIOUR_SPEC};
+use simple_file;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BehaviourNode {
+    Attack,
+    Search,
+    Patrol,
+    Flee,
+    Guard,
+    Wander,
+}
+
+// used by behaviour types missing from the config
+const DEFAULT_NODES: &'static [BehaviourNode] = &[
+    BehaviourNode::Attack,
+    BehaviourNode::Search,
+    BehaviourNode::Patrol,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviourDesc {
+    #[serde(rename = "type")]
+    pub behaviour_type: BehaviourType,
+    pub nodes: Vec<BehaviourNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviourSpec {
+    pub behaviour: Vec<BehaviourDesc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BehaviourConfig {
+    nodes: HashMap<BehaviourType, Vec<BehaviourNode>>,
+}
+
+impl Default for BehaviourConfig {
+    fn default() -> Self {
+        BehaviourConfig {
+            nodes: HashMap::new(),
+        }
+    }
+}
+
+impl BehaviourConfig {
+    pub fn load() -> Self {
+        simple_file::read_toml::<_, BehaviourSpec>(resources::res_path(BEHAVIOUR_SPEC))
+            .map(|spec| Self::from_descs(spec.behaviour))
+            .expect("Failed to read behaviour spec")
+    }
+
+    pub fn from_descs(descs: Vec<BehaviourDesc>) -> Self {
+        let mut config = Self::default();
+        for desc in descs {
+            config.nodes.insert(desc.behaviour_type, desc.nodes);
+        }
+        config
+    }
+
+    // Returns the nodes of the given behaviour type, in order of priority.
+    pub fn nodes(&self, behaviour_type: BehaviourType) -> &[BehaviourNode] {
+        self.nodes.get(&behaviour_type)
+            .map(|nodes| nodes.as_slice())
+            .unwrap_or(DEFAULT_NODES)
+    }
+}
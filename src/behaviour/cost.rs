@@ -0,0 +1,44 @@
+use knowledge::PlayerKnowledgeCell;
+use content::{WaterDepth, KeyRing, can_unlock};
+
+pub const FLOOR_COST: u32 = 1;
+
+// opening a door takes a turn, and makes noise
+pub const CLOSED_DOOR_COST: u32 = 4;
+
+// wading and swimming are slow
+pub const SHALLOW_WATER_COST: u32 = 2;
+pub const DEEP_WATER_COST: u32 = 5;
+
+// avoid standing where the veil is about to be
+pub const VEIL_NEXT_COST: u32 = 6;
+
+// Whether an npc carrying the given keys could get into a cell,
+// opening a door if necessary.
+pub fn can_enter(cell: &PlayerKnowledgeCell, key_ring: Option<&KeyRing>) -> bool {
+    !cell.solid || (cell.door.is_some() && can_unlock(cell.lock, key_ring))
+}
+
+// Cost of an npc carrying the given keys entering a cell, based on
+// what it knows about the cell.
+pub fn movement_cost(cell: &PlayerKnowledgeCell, key_ring: Option<&KeyRing>) -> Option<u32> {
+    let mut cost = if cell.solid {
+        if can_enter(cell, key_ring) {
+            CLOSED_DOOR_COST
+        } else {
+            return None;
+        }
+    } else {
+        match cell.water_depth {
+            Some(WaterDepth::Shallow) => SHALLOW_WATER_COST,
+            Some(WaterDepth::Deep) => DEEP_WATER_COST,
+            None => FLOOR_COST,
+        }
+    };
+
+    if cell.veil_cell.next {
+        cost += VEIL_NEXT_COST;
+    }
+
+    Some(cost)
+}
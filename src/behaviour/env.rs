@@ -1,13 +1,16 @@
 use grid_search::SearchEnv;
+use behaviour::FlowField;
 
 pub struct BehaviourEnv {
     pub search_env: SearchEnv,
+    pub flow_field: FlowField,
 }
 
 impl BehaviourEnv {
     pub fn new(width: usize, height: usize) -> Self {
         BehaviourEnv {
             search_env: SearchEnv::new(width, height),
+            flow_field: FlowField::new(width, height),
         }
     }
 }
@@ -0,0 +1,44 @@
+use knowledge::PlayerKnowledgeGrid;
+use content::ActionType;
+use direction::DirectionsCardinal;
+use entity_store::{EntityId, EntityStore};
+use cgmath::Vector2;
+
+fn distance2(a: Vector2<i32>, b: Vector2<i32>) -> i32 {
+    let delta = a - b;
+    delta.x * delta.x + delta.y * delta.y
+}
+
+// Steps to whichever neighbouring cell is furthest from the player.
+pub fn flee(id: EntityId,
+            entity_store: &EntityStore,
+            knowledge: &PlayerKnowledgeGrid,
+            time: u64) -> Option<ActionType> {
+
+    let position = *entity_store.position.get(&id).expect("Missing position");
+
+    let player_coord = if let Some(player_coord) = knowledge.player_coord() {
+        player_coord
+    } else {
+        return None;
+    };
+
+    let mut best_direction = None;
+    let mut best_distance2 = distance2(position, player_coord);
+
+    for direction in DirectionsCardinal {
+        let coord = position + direction.vector();
+        if let Some(cell) = knowledge.get(coord) {
+            if cell.solid || (cell.enemy.is_some() && cell.is_visible(time)) {
+                continue;
+            }
+            let distance2 = distance2(coord, player_coord);
+            if distance2 > best_distance2 {
+                best_distance2 = distance2;
+                best_direction = Some(direction);
+            }
+        }
+    }
+
+    best_direction.map(|direction| ActionType::Walk(id, direction))
+}
@@ -0,0 +1,92 @@
+use cgmath::Vector2;
+use entity_store::{EntityStore, EntityStoreChange};
+use spatial_hash::{SpatialHashTable, SpatialHashCell};
+use grid_search::DijkstraMap;
+use direction::{Direction, DirectionsCardinal};
+use behaviour::cost::{FLOOR_COST, CLOSED_DOOR_COST, SHALLOW_WATER_COST, DEEP_WATER_COST, VEIL_NEXT_COST};
+use content::WaterDepth;
+
+// doesn't bother mapping cells further than this from the player
+const MAX_COST: u32 = 200;
+
+// Like movement_cost, but from the true state of the cell rather
+// than what any one npc knows about it.
+fn cost(cell: &SpatialHashCell) -> Option<u32> {
+    let mut cost = if cell.solid_count == 0 {
+        match WaterDepth::of_cell(cell) {
+            Some(WaterDepth::Shallow) => SHALLOW_WATER_COST,
+            Some(WaterDepth::Deep) => DEEP_WATER_COST,
+            None => FLOOR_COST,
+        }
+    } else if cell.door_set.is_empty() || cell.lock_count > 0 {
+        // npcs with keys don't use the flow field, so locked doors are impassable
+        return None;
+    } else {
+        CLOSED_DOOR_COST
+    };
+
+    if cell.veil_next_count > 0 {
+        cost += VEIL_NEXT_COST;
+    }
+
+    Some(cost)
+}
+
+// Map of the level leading towards the player, shared by all npcs
+// chasing the player. It's only recomputed when the player moves, or
+// when something which affects movement changes.
+#[derive(Debug)]
+pub struct FlowField {
+    map: DijkstraMap,
+    player_coord: Option<Vector2<i32>>,
+    dirty: bool,
+}
+
+impl FlowField {
+    pub fn new(width: usize, height: usize) -> Self {
+        FlowField {
+            map: DijkstraMap::new(width, height),
+            player_coord: None,
+            dirty: true,
+        }
+    }
+
+    // Marks the flow field for recomputation if the change affects
+    // where npcs can move. Must be called before the change is committed.
+    pub fn invalidate_on_change(&mut self, change: &EntityStoreChange, entity_store: &EntityStore) {
+        if self.dirty {
+            return;
+        }
+
+        if !change.solid.is_empty() || !change.door_state.is_empty() || !change.lock.is_empty() ||
+            !change.water.is_empty() || !change.deep_water.is_empty() || !change.veil_next.is_empty() {
+            self.dirty = true;
+            return;
+        }
+
+        for (id, _) in change.position.iter() {
+            if entity_store.solid.contains(id) {
+                self.dirty = true;
+                return;
+            }
+        }
+    }
+
+    fn update(&mut self, player_coord: Vector2<i32>, spatial_hash: &SpatialHashTable) {
+        if !self.dirty && self.player_coord == Some(player_coord) {
+            return;
+        }
+
+        self.map.compute(spatial_hash, Some(player_coord), DirectionsCardinal, cost, MAX_COST);
+        self.player_coord = Some(player_coord);
+        self.dirty = false;
+    }
+
+    // Returns the direction to step from the given position
+    // to get closer to the player.
+    pub fn next_direction(&mut self, position: Vector2<i32>, player_coord: Vector2<i32>,
+                          spatial_hash: &SpatialHashTable) -> Option<Direction> {
+        self.update(player_coord, spatial_hash);
+        self.map.descend(position, DirectionsCardinal)
+    }
+}
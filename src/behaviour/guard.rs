@@ -0,0 +1,38 @@
+use behaviour::{BehaviourEnv, BehaviourState, movement_cost};
+use knowledge::PlayerKnowledgeGrid;
+use content::ActionType;
+use direction::DirectionsCardinal;
+use entity_store::{EntityId, EntityStore};
+use grid_search::astar;
+
+// Returns to the position at which the npc started, and stays there.
+pub fn guard(id: EntityId,
+             entity_store: &EntityStore,
+             knowledge: &PlayerKnowledgeGrid,
+             env: &mut BehaviourEnv,
+             state: &mut BehaviourState) -> Option<ActionType> {
+
+    let position = *entity_store.position.get(&id).expect("Missing position");
+
+    let post = state.post;
+
+    if position == post {
+        return Some(ActionType::Null);
+    }
+
+    let key_ring = entity_store.key_ring.get(&id);
+    if let Err(_) = astar(&mut env.search_env, knowledge, position, DirectionsCardinal,
+                          post, |cell| movement_cost(cell, key_ring), &mut state.path) {
+        return None;
+    }
+
+    state.path_idx = 0;
+
+    if let Some(step) = state.current_step() {
+        state.path_idx += 1;
+        state.prev_step = Some(step);
+        Some(ActionType::Walk(id, step.direction()))
+    } else {
+        None
+    }
+}
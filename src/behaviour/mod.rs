@@ -1,8 +1,21 @@
 pub mod patrol;
 pub mod attack;
+pub mod search;
+pub mod flee;
+pub mod guard;
+pub mod wander;
 
 mod state;
-pub use self::state::BehaviourState;
+pub use self::state::{BehaviourState, AlertState};
+
+mod cost;
+pub use self::cost::{movement_cost, can_enter};
+
+mod flow_field;
+pub use self::flow_field::FlowField;
 
 mod env;
 pub use self::env::BehaviourEnv;
+
+pub mod config;
+pub use self::config::{BehaviourConfig, BehaviourNode};
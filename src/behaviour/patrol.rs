@@ -1,10 +1,10 @@
-use behaviour::{BehaviourEnv, BehaviourState};
+use behaviour::{BehaviourEnv, BehaviourState, movement_cost, can_enter};
 use knowledge::{PlayerKnowledgeGrid, PlayerKnowledgeCell};
 use observation::ObservationMetadata;
-use content::{ActionType, DoorState};
+use content::{ActionType, DoorState, KeyRing};
 use direction::DirectionsCardinal;
 use entity_store::{EntityId, EntityStore};
-use grid_search::{bfs_best, SearchEnv, Step};
+use grid_search::{bfs_best, astar, SearchEnv, Step};
 use invert_ord::InvertOrd;
 use cgmath::Vector2;
 
@@ -12,6 +12,7 @@ const BFS_MAX: usize = 500;
 
 fn maybe_make_step(position: Vector2<i32>,
                    knowledge: &PlayerKnowledgeGrid,
+                   key_ring: Option<&KeyRing>,
                    observation_metadata: ObservationMetadata,
                    time: u64,
                    state: &mut BehaviourState) -> Option<Step> {
@@ -28,7 +29,7 @@ fn maybe_make_step(position: Vector2<i32>,
 
     for step in state.path_iter() {
         if let Some(cell) = knowledge.get(step.to_coord()) {
-            if cell.solid && cell.door.is_none() {
+            if !can_enter(cell, key_ring) {
                 return None;
             }
             if !cell.is_visible(time) {
@@ -50,18 +51,49 @@ fn search_score(cell: &PlayerKnowledgeCell) -> InvertOrd<u64> {
     InvertOrd::new(cell.last_updated)
 }
 
+// Returns a step towards the source of the most recent noise heard,
+// forgetting about the noise once its source can be seen or reached.
+fn investigate_step(position: Vector2<i32>,
+                    knowledge: &mut PlayerKnowledgeGrid,
+                    key_ring: Option<&KeyRing>,
+                    time: u64,
+                    state: &mut BehaviourState,
+                    search_env: &mut SearchEnv) -> Option<Step> {
+
+    let dest = if let Some(dest) = knowledge.heard_coord() {
+        dest
+    } else {
+        return None;
+    };
+
+    if position == dest || knowledge.is_visible(dest, time) {
+        knowledge.clear_heard_coord();
+        return None;
+    }
+
+    if let Err(_) = astar(search_env, knowledge, position, DirectionsCardinal,
+                          dest, |cell| movement_cost(cell, key_ring), &mut state.path) {
+        knowledge.clear_heard_coord();
+        return None;
+    }
+
+    state.path_idx = 0;
+    state.path.first()
+}
+
 fn make_step(id: EntityId,
              position: Vector2<i32>,
              knowledge: &PlayerKnowledgeGrid,
+             key_ring: Option<&KeyRing>,
              observation_metadata: ObservationMetadata,
              time: u64,
              state: &mut BehaviourState,
              search_env: &mut SearchEnv) -> Option<Step> {
 
-    if let Some(step) = maybe_make_step(position, knowledge, observation_metadata, time, state) {
+    if let Some(step) = maybe_make_step(position, knowledge, key_ring, observation_metadata, time, state) {
         Some(step)
     } else {
-        let can_enter = |cell: &PlayerKnowledgeCell| {
+        let can_step = |cell: &PlayerKnowledgeCell| {
             if cell.last_updated == 0 {
                 return true;
             }
@@ -70,9 +102,9 @@ fn make_step(id: EntityId,
                     return false;
                 }
             }
-            return !cell.solid || cell.door.is_some();
+            return can_enter(cell, key_ring);
         };
-        bfs_best(search_env, knowledge, position, DirectionsCardinal, search_score, can_enter, &mut state.path, BFS_MAX)
+        bfs_best(search_env, knowledge, position, DirectionsCardinal, search_score, can_step, &mut state.path, BFS_MAX)
             .expect("Failed to search");
         state.path_idx = 0;
         state.path.first()
@@ -81,7 +113,7 @@ fn make_step(id: EntityId,
 
 pub fn patrol(id: EntityId,
               entity_store: &EntityStore,
-              knowledge: &PlayerKnowledgeGrid,
+              knowledge: &mut PlayerKnowledgeGrid,
               observation_metadata: ObservationMetadata,
               time: u64,
               env: &mut BehaviourEnv,
@@ -89,7 +121,12 @@ pub fn patrol(id: EntityId,
 
     let position = *entity_store.position.get(&id).expect("Missing position");
 
-    let step = if let Some(step) = make_step(id, position, knowledge, observation_metadata, time, state, &mut env.search_env) {
+    let key_ring = entity_store.key_ring.get(&id);
+
+    let step = if let Some(step) = investigate_step(position, knowledge, key_ring, time, state, &mut env.search_env) {
+        step
+    } else if let Some(step) = make_step(id, position, knowledge, key_ring, observation_metadata, time, state,
+                                         &mut env.search_env) {
         step
     } else {
         return None;
@@ -0,0 +1,114 @@
+use behaviour::{BehaviourEnv, BehaviourState, AlertState, movement_cost, can_enter};
+use knowledge::{PlayerKnowledgeGrid, PlayerKnowledgeCell};
+use content::{ActionType, KeyRing};
+use direction::DirectionsCardinal;
+use entity_store::{EntityId, EntityStore};
+use grid_search::{bfs_best, astar, SearchEnv, Step};
+use invert_ord::InvertOrd;
+use cgmath::Vector2;
+
+// number of turns spent looking around before giving up
+pub const SEARCH_TURNS: u32 = 16;
+
+// limits the search to cells near the searcher
+const SEARCH_BFS_MAX: usize = 64;
+
+fn search_score(cell: &PlayerKnowledgeCell) -> InvertOrd<u64> {
+    InvertOrd::new(cell.last_updated)
+}
+
+fn investigate_step(position: Vector2<i32>,
+                    dest: Vector2<i32>,
+                    knowledge: &PlayerKnowledgeGrid,
+                    key_ring: Option<&KeyRing>,
+                    state: &mut BehaviourState,
+                    search_env: &mut SearchEnv) -> Option<Step> {
+
+    if let Err(_) = astar(search_env, knowledge, position, DirectionsCardinal,
+                          dest, |cell| movement_cost(cell, key_ring), &mut state.path) {
+        return None;
+    }
+
+    state.path_idx = 0;
+    state.current_step()
+}
+
+fn search_step(position: Vector2<i32>,
+               origin: Vector2<i32>,
+               knowledge: &PlayerKnowledgeGrid,
+               key_ring: Option<&KeyRing>,
+               time: u64,
+               state: &mut BehaviourState,
+               search_env: &mut SearchEnv) -> Option<Step> {
+
+    // keep heading towards the current destination until it comes into view
+    if let Some(destination) = state.path.destination() {
+        if !knowledge.is_visible(destination, time) {
+            if let Some(step) = state.current_step() {
+                if step.from_coord() == position {
+                    return Some(step);
+                }
+            }
+        }
+    }
+
+    // pick the least recently seen cell near where the player was last
+    // seen, then find a way there from the searcher's position
+    if let Err(_) = bfs_best(search_env, knowledge, origin, DirectionsCardinal,
+                             search_score, |cell| can_enter(cell, key_ring), &mut state.path, SEARCH_BFS_MAX) {
+        return None;
+    }
+
+    let destination = if let Some(destination) = state.path.destination() {
+        destination
+    } else {
+        return None;
+    };
+
+    investigate_step(position, destination, knowledge, key_ring, state, search_env)
+}
+
+fn walk(id: EntityId, step: Step, state: &mut BehaviourState) -> ActionType {
+    state.path_idx += 1;
+    state.prev_step = Some(step);
+    ActionType::Walk(id, step.direction())
+}
+
+pub fn search(id: EntityId,
+              entity_store: &EntityStore,
+              knowledge: &mut PlayerKnowledgeGrid,
+              time: u64,
+              env: &mut BehaviourEnv,
+              state: &mut BehaviourState) -> Option<ActionType> {
+
+    let position = *entity_store.position.get(&id).expect("Missing position");
+    let key_ring = entity_store.key_ring.get(&id);
+
+    if let Some(last_player_coord) = knowledge.last_player_coord() {
+        if position != last_player_coord {
+            state.alert = AlertState::Investigating(last_player_coord);
+            if let Some(step) = investigate_step(position, last_player_coord, knowledge, key_ring, state, &mut env.search_env) {
+                return Some(walk(id, step, state));
+            }
+        }
+
+        // the player isn't where they were last seen, so look around nearby
+        knowledge.clear_last_player_coord();
+        state.alert = AlertState::Searching { origin: last_player_coord, turns_remaining: SEARCH_TURNS };
+    }
+
+    if let AlertState::Searching { origin, turns_remaining } = state.alert {
+        if turns_remaining == 0 {
+            state.alert = AlertState::Patrolling;
+            return None;
+        }
+
+        state.alert = AlertState::Searching { origin: origin, turns_remaining: turns_remaining - 1 };
+
+        if let Some(step) = search_step(position, origin, knowledge, key_ring, time, state, &mut env.search_env) {
+            return Some(walk(id, step, state));
+        }
+    }
+
+    None
+}
@@ -1,8 +1,27 @@
 use std::collections::HashSet;
 use entity_store::EntityId;
 use grid_search::{Path, Step, PathIterFrom};
+use cgmath::Vector2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertState {
+    // no idea where the player is
+    Patrolling,
+
+    // the player is in view
+    Chasing,
+
+    // heading to where the player was last seen
+    Investigating(Vector2<i32>),
+
+    // looking around near where the player was last seen
+    Searching { origin: Vector2<i32>, turns_remaining: u32 },
+}
 
 pub struct BehaviourState {
+    pub alert: AlertState,
+    // where the npc was spawned, which guards return to
+    pub post: Vector2<i32>,
     pub opened_doors: HashSet<EntityId>,
     pub prev_step: Option<Step>,
     pub path: Path,
@@ -10,8 +29,10 @@ pub struct BehaviourState {
 }
 
 impl BehaviourState {
-    pub fn new() -> Self {
+    pub fn new(post: Vector2<i32>) -> Self {
         BehaviourState {
+            alert: AlertState::Patrolling,
+            post: post,
             opened_doors: HashSet::new(),
             prev_step: None,
             path: Path::new(),
@@ -0,0 +1,36 @@
+use rand::Rng;
+use knowledge::PlayerKnowledgeGrid;
+use content::ActionType;
+use direction::Direction;
+use entity_store::{EntityId, EntityStore};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+// Steps in a random direction which isn't known to be blocked.
+pub fn wander<R: Rng>(id: EntityId,
+                      entity_store: &EntityStore,
+                      knowledge: &PlayerKnowledgeGrid,
+                      time: u64,
+                      rng: &mut R) -> Option<ActionType> {
+
+    let position = *entity_store.position.get(&id).expect("Missing position");
+
+    let offset = rng.gen::<usize>() % DIRECTIONS.len();
+
+    for i in 0..DIRECTIONS.len() {
+        let direction = DIRECTIONS[(offset + i) % DIRECTIONS.len()];
+        if let Some(cell) = knowledge.get(position + direction.vector()) {
+            if cell.solid || (cell.enemy.is_some() && cell.is_visible(time)) {
+                continue;
+            }
+            return Some(ActionType::Walk(id, direction));
+        }
+    }
+
+    None
+}
@@ -1,4 +1,5 @@
 use std::result;
+use std::collections::HashMap;
 use rand::Rng;
 use knowledge::PlayerKnowledgeGrid;
 use reaction::Reaction;
@@ -6,16 +7,23 @@ use entity_store::*;
 use spatial_hash::*;
 use entity_id_allocator::*;
 use content::ActionType;
-use observation::shadowcast::ShadowcastEnv;
+use observation::FovEnv;
+use observation::FovCacheTable;
+use light_grid::LightGrid;
+use weather::Weather;
+use grid_search;
+use behaviour::BehaviourEnv;
 use policy::*;
 use renderer::GameRenderer;
 use input::GameInput;
 use schedule::{Schedule, ScheduleEntry};
 use player_render;
+use noise;
 
 #[derive(Debug)]
 pub enum Error {
     RenderingFailed(player_render::Error),
+    NoisePropagationFailed(grid_search::Error),
 }
 pub type Result<T> = result::Result<T, Error>;
 
@@ -25,6 +33,12 @@ impl From<player_render::Error> for Error {
     }
 }
 
+impl From<grid_search::Error> for Error {
+    fn from(e: grid_search::Error) -> Self {
+        Error::NoisePropagationFailed(e)
+    }
+}
+
 pub struct CommitEnv<'a, R: 'a + Rng, Ren: 'a + GameRenderer, Inp: 'a + GameInput> {
     pub renderer: &'a mut Ren,
     pub change: &'a mut EntityStoreChange,
@@ -32,7 +46,12 @@ pub struct CommitEnv<'a, R: 'a + Rng, Ren: 'a + GameRenderer, Inp: 'a + GameInpu
     pub spatial_hash: &'a mut SpatialHashTable,
     pub player_knowledge: &'a mut PlayerKnowledgeGrid,
     pub player_id: EntityId,
-    pub shadowcast: &'a mut ShadowcastEnv,
+    pub knowledge: &'a mut HashMap<EntityId, PlayerKnowledgeGrid>,
+    pub behaviour_env: &'a mut BehaviourEnv,
+    pub fov: &'a mut FovEnv,
+    pub fov_cache: &'a mut FovCacheTable,
+    pub light_grid: &'a mut LightGrid,
+    pub weather: &'a Weather,
     pub time: &'a mut u64,
     pub reactions: &'a mut Vec<Reaction>,
     pub action_schedule_entries: &'a mut Vec<ScheduleEntry<ActionType>>,
@@ -58,7 +77,9 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> CommitEnv<'a, R, Ren, Inp> {
             self.spatial_hash,
             *self.time,
             self.player_knowledge,
-            self.shadowcast,
+            self.fov,
+            self.fov_cache,
+            self.light_grid,
             self.renderer
         ).map_err(Error::RenderingFailed)
     }
@@ -137,6 +158,11 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> CommitEnv<'a, R, Ren, Inp> {
 
             self.policy.on_change(self.change, self.entity_store, self.spatial_hash, self.reactions);
 
+            noise::propagate(self.change, self.entity_store, self.spatial_hash,
+                             &mut self.behaviour_env.search_env, self.knowledge,
+                             self.weather.noise_muffling())?;
+            self.behaviour_env.flow_field.invalidate_on_change(self.change, self.entity_store);
+
             for Reaction { action, delay } in self.reactions.drain(..) {
                 self.schedule.insert(action, delay);
             }
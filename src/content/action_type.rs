@@ -1,8 +1,9 @@
 use entity_store::*;
 use entity_id_allocator::EntityIdAllocator;
 use direction::Direction;
-use content::actions;
+use content::{actions, Projectile, StatusEffect};
 use straight_line::InfiniteAbsoluteLineTraverse;
+use cgmath::Vector2;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ActionType {
@@ -10,8 +11,16 @@ pub enum ActionType {
     Walk(EntityId, Direction),
     CloseDoor(EntityId),
     OpenDoor(EntityId),
-    FireBullet(InfiniteAbsoluteLineTraverse),
+    FireWeapon(EntityId, Projectile, InfiniteAbsoluteLineTraverse),
+    Reload(EntityId),
+    PickUp(EntityId, EntityId),
+    DropItem(EntityId, EntityId),
+    UseItem(EntityId, EntityId),
+    CycleWeapon(EntityId),
+    ExpireStatus(EntityId, StatusEffect, u64),
     Remove(EntityId),
+    Smoke(Vector2<i32>),
+    Crack(Vector2<i32>),
 }
 
 impl ActionType {
@@ -19,10 +28,18 @@ impl ActionType {
         match self {
             ActionType::Null => (),
             ActionType::Walk(id, direction) => actions::walk(change, entity_store, id, direction),
-            ActionType::OpenDoor(id) => actions::open_door(change, id),
-            ActionType::CloseDoor(id) => actions::close_door(change, id),
-            ActionType::FireBullet(traverse) => actions::fire_bullet(change, traverse, ids),
+            ActionType::OpenDoor(id) => actions::open_door(change, entity_store, id, ids),
+            ActionType::CloseDoor(id) => actions::close_door(change, entity_store, id, ids),
+            ActionType::FireWeapon(id, projectile, traverse) => actions::fire_weapon(change, entity_store, id, projectile, traverse, ids),
+            ActionType::Reload(id) => actions::reload(change, entity_store, id),
+            ActionType::CycleWeapon(id) => actions::cycle_weapon(change, entity_store, id),
+            ActionType::PickUp(id, item_id) => actions::pick_up(change, entity_store, id, item_id),
+            ActionType::DropItem(id, item_id) => actions::drop_item(change, entity_store, id, item_id),
+            ActionType::UseItem(id, item_id) => actions::use_item(change, entity_store, id, item_id),
+            ActionType::ExpireStatus(id, effect, expiry) => actions::expire_status(change, entity_store, id, effect, expiry),
             ActionType::Remove(id) => actions::remove(change, id, entity_store),
+            ActionType::Smoke(position) => actions::smoke(change, position, ids),
+            ActionType::Crack(position) => actions::crack(change, position, ids),
         }
     }
 }
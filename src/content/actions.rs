@@ -1,8 +1,10 @@
+use std::cmp;
 use entity_store::*;
 use entity_id_allocator::EntityIdAllocator;
 use content::*;
 use direction::Direction;
 use straight_line::InfiniteAbsoluteLineTraverse;
+use cgmath::Vector2;
 
 pub fn walk(change: &mut EntityStoreChange, entity_store: &EntityStore,
             id: EntityId, direction: Direction) {
@@ -13,23 +15,190 @@ pub fn walk(change: &mut EntityStoreChange, entity_store: &EntityStore,
     change.position.insert(id, new);
 }
 
-pub fn open_door(change: &mut EntityStoreChange, id: EntityId) {
+fn make_noise(change: &mut EntityStoreChange, entity_store: &EntityStore,
+              id: EntityId, loudness: u32, ids: &mut EntityIdAllocator) {
+    if let Some(position) = entity_store.position.get(&id) {
+        prototypes::noise(change, ids.allocate(), *position, loudness);
+    }
+}
+
+pub fn open_door(change: &mut EntityStoreChange, entity_store: &EntityStore,
+                 id: EntityId, ids: &mut EntityIdAllocator) {
+    make_noise(change, entity_store, id, OPEN_DOOR_NOISE, ids);
     change.door_state.insert(id, DoorState::Open);
     change.solid.remove(id);
     change.opacity.insert(id, 0.0);
     change.tile.insert(id, ComplexTile::Wall { front: TileType::OpenDoorFront, top: TileType::OpenDoorTop });
 }
 
-pub fn close_door(change: &mut EntityStoreChange, id: EntityId) {
+pub fn close_door(change: &mut EntityStoreChange, entity_store: &EntityStore,
+                  id: EntityId, ids: &mut EntityIdAllocator) {
+    make_noise(change, entity_store, id, CLOSE_DOOR_NOISE, ids);
     change.door_state.insert(id, DoorState::Closed);
     change.solid.insert(id);
     change.opacity.insert(id, 1.0);
     change.tile.insert(id, ComplexTile::Wall { front: TileType::ClosedDoorFront, top: TileType::ClosedDoorTop });
 }
 
-pub fn fire_bullet(change: &mut EntityStoreChange, traverse: InfiniteAbsoluteLineTraverse, ids: &mut EntityIdAllocator) {
-    let bullet_id = ids.allocate();
-    prototypes::bullet(change, bullet_id, traverse);
+fn muzzle_flash(change: &mut EntityStoreChange, position: Vector2<i32>, ids: &mut EntityIdAllocator) {
+    let flash_id = ids.allocate();
+    prototypes::muzzle_flash(change, flash_id, position);
+    prototypes::noise(change, ids.allocate(), position, FIRE_BULLET_NOISE);
+}
+
+pub fn fire_weapon(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId,
+                   projectile: Projectile, traverse: InfiniteAbsoluteLineTraverse, ids: &mut EntityIdAllocator) {
+    let mut arsenal = if let Some(arsenal) = entity_store.weapons.get(&id) {
+        arsenal.clone()
+    } else {
+        return;
+    };
+
+    if let Some(weapon) = arsenal.equipped_mut() {
+        if !weapon.fire() {
+            return;
+        }
+        muzzle_flash(change, traverse.current(), ids);
+        for traverse in spread(traverse, weapon.weapon_type.pellets()) {
+            let projectile_id = ids.allocate();
+            prototypes::projectile(change, projectile_id, projectile, traverse);
+        }
+        delay_turn(change, entity_store, id, weapon.weapon_type.fire_time());
+    }
+
+    change.weapons.insert(id, arsenal);
+}
+
+pub fn reload(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId) {
+    let mut arsenal = if let Some(arsenal) = entity_store.weapons.get(&id) {
+        arsenal.clone()
+    } else {
+        return;
+    };
+
+    if let Some(weapon) = arsenal.equipped_mut() {
+        if weapon.reload() > 0 {
+            delay_turn(change, entity_store, id, weapon.weapon_type.reload_time());
+        }
+    }
+
+    change.weapons.insert(id, arsenal);
+}
+
+pub fn cycle_weapon(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId) {
+    if let Some(arsenal) = entity_store.weapons.get(&id) {
+        let mut arsenal = arsenal.clone();
+        arsenal.cycle();
+        change.weapons.insert(id, arsenal);
+    }
+}
+
+pub fn pick_up(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId, item_id: EntityId) {
+    if !entity_store.item.contains_key(&item_id) {
+        return;
+    }
+    // items can only be picked up from underfoot
+    if entity_store.position.get(&id) != entity_store.position.get(&item_id) {
+        return;
+    }
+    if let Some(inventory) = entity_store.inventory.get(&id) {
+        let mut inventory = inventory.clone();
+        if inventory.insert(item_id) {
+            change.position.remove(item_id);
+            change.inventory.insert(id, inventory);
+        }
+    }
+}
+
+pub fn drop_item(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId, item_id: EntityId) {
+    if let (Some(position), Some(inventory)) = (entity_store.position.get(&id), entity_store.inventory.get(&id)) {
+        let mut inventory = inventory.clone();
+        if inventory.remove(item_id) {
+            change.position.insert(item_id, *position);
+            change.inventory.insert(id, inventory);
+        }
+    }
+}
+
+pub fn use_item(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId, item_id: EntityId) {
+    let mut inventory = match entity_store.inventory.get(&id) {
+        Some(inventory) if inventory.contains(item_id) => inventory.clone(),
+        _ => return,
+    };
+    let item_type = match entity_store.item.get(&item_id) {
+        Some(item_type) => *item_type,
+        None => return,
+    };
+
+    match item_type {
+        ItemType::Bandage => {
+            let hit_points = match entity_store.hit_points.get(&id) {
+                Some(hit_points) if *hit_points < PLAYER_HIT_POINTS => *hit_points,
+                _ => return,
+            };
+            change.hit_points.insert(id, cmp::min(hit_points + BANDAGE_HEAL, PLAYER_HIT_POINTS));
+        }
+        ItemType::Ammo(weapon_type) => {
+            let mut arsenal = match entity_store.weapons.get(&id) {
+                Some(arsenal) => arsenal.clone(),
+                None => return,
+            };
+            if let Some(weapon) = arsenal.weapon_mut(weapon_type) {
+                weapon.reserve += ammo_box_rounds(weapon_type);
+            } else {
+                // no use without the weapon to go with it
+                return;
+            }
+            change.weapons.insert(id, arsenal);
+        }
+    }
+
+    inventory.remove(item_id);
+    change.inventory.insert(id, inventory);
+    change.remove_entity(item_id, entity_store);
+}
+
+// Removes a status effect when its time is up, unless it has since
+// been extended or removed.
+pub fn expire_status(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId,
+                     effect: StatusEffect, expiry: u64) {
+    let expired = entity_store.status_effects.get(&id).map(|effects| {
+        effects.expiry(effect) == Some(expiry)
+    }).unwrap_or(false);
+
+    if expired {
+        remove_status(change, entity_store, id, effect);
+    }
+}
+
+pub fn remove_status(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId, effect: StatusEffect) {
+    if let Some(effects) = post_change_get!(entity_store, change, id, status_effects).cloned() {
+        let mut effects = effects;
+        if effects.remove(effect) {
+            if effects.is_empty() {
+                change.status_effects.remove(id);
+            } else {
+                change.status_effects.insert(id, effects);
+            }
+        }
+    }
+}
+
+fn delay_turn(change: &mut EntityStoreChange, entity_store: &EntityStore, id: EntityId, delay: u64) {
+    if delay > 0 {
+        let current = post_change_get!(entity_store, change, id, turn_delay).cloned().unwrap_or(0);
+        change.turn_delay.insert(id, current + delay);
+    }
+}
+
+pub fn smoke(change: &mut EntityStoreChange, position: Vector2<i32>, ids: &mut EntityIdAllocator) {
+    for _ in 0..SMOKE_PUFF_COUNT {
+        prototypes::smoke(change, ids.allocate(), position);
+    }
+}
+
+pub fn crack(change: &mut EntityStoreChange, position: Vector2<i32>, ids: &mut EntityIdAllocator) {
+    prototypes::cracks(change, ids.allocate(), position);
 }
 
 pub fn remove(change: &mut EntityStoreChange, id: EntityId, entity_store: &EntityStore) {
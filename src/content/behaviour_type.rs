@@ -1,5 +1,7 @@
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BehaviourType {
     Player,
     Undead,
+    Sentry,
+    Skulker,
 }
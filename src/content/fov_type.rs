@@ -0,0 +1,14 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FovType {
+    // octant shadowcast, which supports partially opaque cells
+    Shadowcast,
+
+    // quadrant shadowcast, where seeing implies being seen
+    SymmetricShadowcast,
+
+    // quadrant shadowcast, which reveals any cell partially in view
+    Permissive,
+
+    // sees everything
+    Omniscient,
+}
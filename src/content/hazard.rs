@@ -0,0 +1,7 @@
+pub const PLAYER_HIT_POINTS: u32 = 10;
+
+// veil creatures appear within this distance of the entity that summoned them
+pub const VEIL_SPAWN_RADIUS: i32 = 6;
+
+// number of random cells tried when looking for somewhere to spawn a veil creature
+pub const VEIL_SPAWN_ATTEMPTS: usize = 16;
@@ -0,0 +1,86 @@
+use std::slice;
+use entity_store::EntityId;
+use content::{TileType, WeaponType};
+
+// most items an inventory can hold
+pub const INVENTORY_CAPACITY: usize = 8;
+
+pub const BANDAGE_HEAL: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemType {
+    Bandage,
+    // spare rounds for a type of weapon
+    Ammo(WeaponType),
+}
+
+impl ItemType {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            ItemType::Bandage => "Bandage",
+            ItemType::Ammo(WeaponType::Pistol) => "Pistol ammo",
+            ItemType::Ammo(WeaponType::Shotgun) => "Shotgun shells",
+            ItemType::Ammo(WeaponType::Rifle) => "Rifle rounds",
+        }
+    }
+
+    pub fn tile(self) -> TileType {
+        match self {
+            ItemType::Bandage => TileType::Bandage,
+            ItemType::Ammo(_) => TileType::AmmoBox,
+        }
+    }
+}
+
+// rounds in a box of ammo for a type of weapon
+pub fn ammo_box_rounds(weapon_type: WeaponType) -> u32 {
+    weapon_type.capacity() * 2
+}
+
+// The items carried by an entity. Carried items are still entities,
+// but have no position until they are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inventory {
+    items: Vec<EntityId>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= INVENTORY_CAPACITY
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.items.contains(&id)
+    }
+
+    // Returns false if there was no room for the item.
+    pub fn insert(&mut self, id: EntityId) -> bool {
+        if self.is_full() || self.contains(id) {
+            return false;
+        }
+        self.items.push(id);
+        true
+    }
+
+    // Returns false if the item wasn't in the inventory.
+    pub fn remove(&mut self, id: EntityId) -> bool {
+        if let Some(index) = self.items.iter().position(|item| *item == id) {
+            self.items.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn iter(&self) -> slice::Iter<EntityId> {
+        self.items.iter()
+    }
+}
@@ -0,0 +1,38 @@
+use std::collections::BTreeSet;
+
+// Identifies which doors a key opens. A locked door opens for anyone
+// carrying a key with the same id as its lock.
+pub type KeyId = u32;
+
+// The keys carried by an entity
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRing {
+    keys: BTreeSet<KeyId>,
+}
+
+impl KeyRing {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn insert(&mut self, key: KeyId) {
+        self.keys.insert(key);
+    }
+
+    pub fn contains(&self, key: KeyId) -> bool {
+        self.keys.contains(&key)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+// Whether an entity carrying the given keys (if any) can get through
+// a door with the given lock (if any).
+pub fn can_unlock(lock: Option<KeyId>, key_ring: Option<&KeyRing>) -> bool {
+    match lock {
+        Some(lock) => key_ring.map(|k| k.contains(lock)).unwrap_or(false),
+        None => true,
+    }
+}
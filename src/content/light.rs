@@ -0,0 +1,107 @@
+use std::ops::{Add, AddAssign, Mul};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LightColour {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+impl LightColour {
+    pub fn new(r: f64, g: f64, b: f64) -> Self {
+        LightColour {
+            r: r,
+            g: g,
+            b: b,
+        }
+    }
+
+    pub fn black() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+
+    pub fn white() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+
+    // Perceived brightness of the colour, used to decide whether a
+    // cell is lit well enough to be seen.
+    pub fn intensity(&self) -> f64 {
+        0.299 * self.r + 0.587 * self.g + 0.114 * self.b
+    }
+
+    pub fn saturate(self) -> Self {
+        Self::new(self.r.min(1.0), self.g.min(1.0), self.b.min(1.0))
+    }
+}
+
+impl Default for LightColour {
+    fn default() -> Self {
+        Self::black()
+    }
+}
+
+impl Add for LightColour {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.r + rhs.r, self.g + rhs.g, self.b + rhs.b)
+    }
+}
+
+impl AddAssign for LightColour {
+    fn add_assign(&mut self, rhs: Self) {
+        self.r += rhs.r;
+        self.g += rhs.g;
+        self.b += rhs.b;
+    }
+}
+
+impl Mul<f64> for LightColour {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.r * rhs, self.g * rhs, self.b * rhs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Light {
+    pub radius: u32,
+    pub colour: LightColour,
+}
+
+impl Light {
+    pub fn new(radius: u32, colour: LightColour) -> Self {
+        Light {
+            radius: radius,
+            colour: colour,
+        }
+    }
+}
+
+// light levels that determine how far away a cell can be seen
+pub const LIGHT_DIM_THRESHOLD: f64 = 0.15;
+pub const LIGHT_BRIGHT_THRESHOLD: f64 = 0.6;
+
+// cells closer than this are visible in the dark
+pub const DARK_VISION_DISTANCE: u32 = 2;
+
+// brightly lit cells can be seen from this many times an
+// observer's vision distance
+pub const LIT_VISION_MULTIPLIER: u32 = 2;
+
+pub const AMBIENT_OUTSIDE: LightColour = LightColour { r: 0.35, g: 0.35, b: 0.45 };
+
+// just bright enough that unlit rooms can be seen within an
+// observer's vision distance, so only lit cells are seen from further
+pub const AMBIENT_INSIDE: LightColour = LightColour { r: 0.2, g: 0.18, b: 0.16 };
+pub const VEIL_GLOW: LightColour = LightColour { r: 0.2, g: 0.05, b: 0.3 };
+
+pub const LAMP_RADIUS: u32 = 7;
+pub const LAMP_COLOUR: LightColour = LightColour { r: 1.0, g: 0.8, b: 0.5 };
+pub const MUZZLE_FLASH_RADIUS: u32 = 4;
+pub const MUZZLE_FLASH_COLOUR: LightColour = LightColour { r: 1.0, g: 0.9, b: 0.6 };
+pub const FIRE_RADIUS: u32 = 5;
+pub const FIRE_COLOUR: LightColour = LightColour { r: 1.0, g: 0.6, b: 0.3 };
+
+// number of frames a muzzle flash lasts
+pub const MUZZLE_FLASH_DURATION: u64 = 4;
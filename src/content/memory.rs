@@ -0,0 +1,17 @@
+use content::TileType;
+
+// number of time steps after which remembered cells are drawn fully stale
+pub const MEMORY_FADE_TIME: u64 = 1800;
+
+// number of time steps after which the last known position of an enemy is forgotten
+pub const GHOST_MAX_AGE: u64 = 3600;
+
+pub const GHOST_TILE_PRIORITY: u8 = 4;
+
+// drawn at the last known positions of enemies, from the most to the
+// least recently seen, each for an equal share of GHOST_MAX_AGE
+pub const GHOST_TILES: [TileType; 3] = [
+    TileType::EnemyGhost,
+    TileType::EnemyGhostFading,
+    TileType::EnemyGhostFaint,
+];
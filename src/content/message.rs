@@ -0,0 +1,19 @@
+// Things the player is told about as they happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    DoorLocked,
+    KeyPickedUp,
+    ConsumedByVeil,
+    BurnedToDeath,
+}
+
+impl Message {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Message::DoorLocked => "The door is locked.",
+            Message::KeyPickedUp => "You pick up a key.",
+            Message::ConsumedByVeil => "You were consumed by the veil.",
+            Message::BurnedToDeath => "You burned to death.",
+        }
+    }
+}
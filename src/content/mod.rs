@@ -7,6 +7,19 @@ mod behaviour_type;
 mod veil_step_info;
 mod water;
 mod rain;
+mod light;
+mod noise;
+mod fov_type;
+mod opacity;
+mod memory;
+mod hazard;
+mod key;
+mod message;
+mod structure;
+mod projectile;
+mod weapon;
+mod item;
+mod status;
 pub use self::tile_type::*;
 pub use self::overlay_type::*;
 pub use self::complex_tile::*;
@@ -16,5 +29,18 @@ pub use self::behaviour_type::*;
 pub use self::veil_step_info::*;
 pub use self::water::*;
 pub use self::rain::*;
+pub use self::light::*;
+pub use self::noise::*;
+pub use self::fov_type::*;
+pub use self::opacity::*;
+pub use self::memory::*;
+pub use self::hazard::*;
+pub use self::key::*;
+pub use self::message::*;
+pub use self::structure::*;
+pub use self::projectile::*;
+pub use self::weapon::*;
+pub use self::item::*;
+pub use self::status::*;
 pub mod prototypes;
 pub mod actions;
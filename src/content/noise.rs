@@ -0,0 +1,12 @@
+// loudness of noises made by actions
+pub const FIRE_BULLET_NOISE: u32 = 24;
+pub const OPEN_DOOR_NOISE: u32 = 8;
+pub const CLOSE_DOOR_NOISE: u32 = 8;
+
+// how far an npc's call to its allies carries
+pub const ALERT_LOUDNESS: u32 = 16;
+
+// amount by which a noise is reduced when it enters a cell
+pub const NOISE_ATTENUATION: u32 = 1;
+pub const CLOSED_DOOR_NOISE_ATTENUATION: u32 = 6;
+pub const WALL_NOISE_ATTENUATION: u32 = 12;
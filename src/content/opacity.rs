@@ -0,0 +1,21 @@
+// Cells seen with less than this much visibility are obscured,
+// hiding any creatures in them.
+pub const OBSCURED_VISIBILITY: f64 = 0.5;
+
+pub const TALL_GRASS_OPACITY: f64 = 0.3;
+
+// each bullet hitting something solid produces this many puffs of smoke
+pub const SMOKE_PUFF_COUNT: usize = 4;
+pub const SMOKE_INITIAL_OPACITY: f64 = 0.4;
+pub const SMOKE_FRAME_RATE: u64 = 15;
+pub const SMOKE_DECAY: f64 = 0.01;
+
+// chance of a puff of smoke moving to a neighbouring cell each update
+pub const SMOKE_DRIFT_PROBABILITY: f64 = 0.25;
+
+pub const HEAVY_RAIN_MIN_OPACITY: f64 = 0.1;
+pub const HEAVY_RAIN_MAX_OPACITY: f64 = 0.35;
+
+// Heavy rain thickens in this many steps rather than smoothly, as
+// each change of opacity invalidates cached fields of view.
+pub const HEAVY_RAIN_OPACITY_STEPS: u32 = 4;
@@ -1,3 +1,5 @@
+use enum_primitive::FromPrimitive;
+
 enum_from_primitive! {
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum OverlayType {
@@ -8,6 +10,16 @@ pub enum OverlayType {
     Veil,
     VeilCurrent,
     VeilNext,
+    VeilWarning,
+    VeilForecast1,
+    VeilForecast2,
+    VeilForecast3,
+    VeilForecast4,
+    VeilForecast5,
+    VeilForecast6,
+    VeilForecast7,
+    VeilForecast8,
+    VeilForecast9,
 }
 }
 
@@ -21,8 +33,28 @@ impl OverlayType {
             OverlayType::Veil => "Veil",
             OverlayType::VeilCurrent => "VeilCurrent",
             OverlayType::VeilNext => "VeilNext",
+            OverlayType::VeilWarning => "VeilWarning",
+            OverlayType::VeilForecast1 => "VeilForecast1",
+            OverlayType::VeilForecast2 => "VeilForecast2",
+            OverlayType::VeilForecast3 => "VeilForecast3",
+            OverlayType::VeilForecast4 => "VeilForecast4",
+            OverlayType::VeilForecast5 => "VeilForecast5",
+            OverlayType::VeilForecast6 => "VeilForecast6",
+            OverlayType::VeilForecast7 => "VeilForecast7",
+            OverlayType::VeilForecast8 => "VeilForecast8",
+            OverlayType::VeilForecast9 => "VeilForecast9",
+        }
+    }
+
+    // Returns the overlay counting down the given number of turns until
+    // the veil arrives, if there is one.
+    pub fn veil_forecast(turns: u32) -> Option<Self> {
+        if turns == 0 || turns as usize > MAX_VEIL_FORECAST_OVERLAY {
+            return None;
         }
+        OverlayType::from_usize(OverlayType::VeilForecast1 as usize + turns as usize - 1)
     }
 }
 
-pub const NUM_OVERLAYS: usize = 7;
+pub const NUM_OVERLAYS: usize = 17;
+pub const MAX_VEIL_FORECAST_OVERLAY: usize = 9;
@@ -0,0 +1,31 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProjectileType {
+    Bullet,
+    Pellet,
+    RifleRound,
+}
+
+// The properties of a projectile in flight. Penetration and ricochets
+// count down as the projectile passes through targets and bounces off
+// walls. The range remaining is tracked separately as it travels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Projectile {
+    // frames taken to move one cell, so lower is faster
+    pub frames_per_step: u64,
+    // cells travelled before the projectile falls
+    pub range: u32,
+    pub damage: u32,
+    // number of shootable entities passed through
+    pub penetration: u32,
+    // number of times the projectile bounces off walls
+    pub ricochets: u32,
+}
+
+// used by projectile types missing from the config
+pub const DEFAULT_PROJECTILE: Projectile = Projectile {
+    frames_per_step: 2,
+    range: 64,
+    damage: 1,
+    penetration: 0,
+    ricochets: 0,
+};
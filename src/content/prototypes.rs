@@ -14,10 +14,32 @@ pub fn player(change: &mut EntityStoreChange, entity_id: EntityId, position: Vec
     change.turn_period.insert(entity_id, 1);
     change.behaviour_type.insert(entity_id, BehaviourType::Player);
     change.vision_distance.insert(entity_id, 20);
+    change.fov_type.insert(entity_id, FovType::Shadowcast);
     change.door_opener.insert(entity_id);
+    change.hit_points.insert(entity_id, PLAYER_HIT_POINTS);
+    change.key_ring.insert(entity_id, KeyRing::new());
+    change.weapons.insert(entity_id, Arsenal::new(vec![
+        Weapon::new(WeaponType::Pistol, 18),
+        Weapon::new(WeaponType::Shotgun, 8),
+        Weapon::new(WeaponType::Rifle, 8),
+    ]));
+    change.inventory.insert(entity_id, Inventory::new());
 }
 
 pub fn undead(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    undead_with_behaviour(change, entity_id, position, BehaviourType::Undead, FovType::Shadowcast);
+}
+
+pub fn sentry(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    undead_with_behaviour(change, entity_id, position, BehaviourType::Sentry, FovType::Permissive);
+}
+
+pub fn skulker(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    undead_with_behaviour(change, entity_id, position, BehaviourType::Skulker, FovType::SymmetricShadowcast);
+}
+
+fn undead_with_behaviour(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>,
+                         behaviour_type: BehaviourType, fov_type: FovType) {
     change.position.insert(entity_id, position);
     change.enemy.insert(entity_id);
     change.npc.insert(entity_id);
@@ -26,8 +48,9 @@ pub fn undead(change: &mut EntityStoreChange, entity_id: EntityId, position: Vec
     change.tile_priority.insert(entity_id, 4);
     change.forgetable.insert(entity_id);
     change.turn_period.insert(entity_id, 2);
-    change.behaviour_type.insert(entity_id, BehaviourType::Undead);
+    change.behaviour_type.insert(entity_id, behaviour_type);
     change.vision_distance.insert(entity_id, 10);
+    change.fov_type.insert(entity_id, fov_type);
     change.door_opener.insert(entity_id);
     change.shootable.insert(entity_id);
     change.veil_change.insert(entity_id);
@@ -57,6 +80,7 @@ pub fn wall(change: &mut EntityStoreChange, entity_id: EntityId, position: Vecto
     change.opacity.insert(entity_id, 1.0);
     change.tile.insert(entity_id, ComplexTile::Wall { front: TileType::WallFront, top: TileType::WallTop });
     change.tile_priority.insert(entity_id, 2);
+    change.integrity.insert(entity_id, WALL_INTEGRITY);
 }
 
 pub fn stone_wall(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
@@ -65,12 +89,14 @@ pub fn stone_wall(change: &mut EntityStoreChange, entity_id: EntityId, position:
     change.opacity.insert(entity_id, 1.0);
     change.tile.insert(entity_id, ComplexTile::Wall { front: TileType::StoneWallFront, top: TileType::StoneWallTop });
     change.tile_priority.insert(entity_id, 2);
+    change.integrity.insert(entity_id, STONE_WALL_INTEGRITY);
 }
 
 pub fn door(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, state: DoorState) {
     change.position.insert(entity_id, position);
     change.tile_priority.insert(entity_id, 2);
     change.door_state.insert(entity_id, state);
+    change.integrity.insert(entity_id, DOOR_INTEGRITY);
 
     if state == DoorState::Closed {
         change.tile.insert(entity_id, ComplexTile::Wall { front: TileType::ClosedDoorFront, top: TileType::ClosedDoorTop });
@@ -82,31 +108,76 @@ pub fn door(change: &mut EntityStoreChange, entity_id: EntityId, position: Vecto
     }
 }
 
-pub fn rain<R: Rng>(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, rng: &mut R) {
+pub fn locked_door(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, lock: KeyId) {
+    door(change, entity_id, position, DoorState::Closed);
+    change.lock.insert(entity_id, lock);
+}
+
+pub fn key(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, key: KeyId) {
+    change.position.insert(entity_id, position);
+    change.tile.insert(entity_id, ComplexTile::Simple(TileType::Key));
+    change.tile_priority.insert(entity_id, 2);
+    change.key.insert(entity_id, key);
+}
+
+pub fn item(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, item_type: ItemType) {
+    change.position.insert(entity_id, position);
+    change.tile.insert(entity_id, ComplexTile::Simple(item_type.tile()));
+    change.tile_priority.insert(entity_id, 2);
+    change.item.insert(entity_id, item_type);
+}
+
+pub fn rain<R: Rng>(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>,
+                    delta: Vector2<i32>, rng: &mut R) {
     change.rain.insert(entity_id);
     change.position.insert(entity_id, position);
     change.tile_priority.insert(entity_id, 2);
     change.tile.insert(entity_id, ComplexTile::Simple(TileType::Rain));
     change.forgetable.insert(entity_id);
 
-    let length = 8;
-    let mut trajectory = FiniteAbsoluteLineTraverse::new_offset(position, Vector2::new(0, length));
-    for _ in 0..(rng.next_u32() % length as u32) {
+    let mut trajectory = FiniteAbsoluteLineTraverse::new_offset(position, delta);
+    for _ in 0..(rng.next_u32() % delta.y as u32) {
         trajectory.step_in_place();
     }
     change.finite_trajectory.insert(entity_id, trajectory);
 }
 
-pub fn bullet(change: &mut EntityStoreChange, entity_id: EntityId, mut traverse: InfiniteAbsoluteLineTraverse) {
+pub fn projectile(change: &mut EntityStoreChange, entity_id: EntityId, projectile: Projectile,
+                  mut traverse: InfiniteAbsoluteLineTraverse) {
     change.position.insert(entity_id, traverse.step_in_place());
     change.infinite_trajectory.insert(entity_id, traverse);
     change.tile_priority.insert(entity_id, 2);
     change.tile.insert(entity_id, ComplexTile::Simple(TileType::Bullet));
     change.forgetable.insert(entity_id);
     change.realtime.insert(entity_id);
-    change.bullet.insert(entity_id);
+    change.projectile.insert(entity_id, projectile);
+    change.projectile_range.insert(entity_id, projectile.range);
     change.collider.insert(entity_id);
-    change.realtime_period.insert(entity_id, 2);
+    change.realtime_period.insert(entity_id, projectile.frames_per_step);
+}
+
+pub fn muzzle_flash(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.light.insert(entity_id, Light::new(MUZZLE_FLASH_RADIUS, MUZZLE_FLASH_COLOUR));
+    change.muzzle_flash.insert(entity_id);
+}
+
+pub fn noise(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, loudness: u32) {
+    change.position.insert(entity_id, position);
+    change.noise.insert(entity_id, loudness);
+}
+
+pub fn lamp(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.light.insert(entity_id, Light::new(LAMP_RADIUS, LAMP_COLOUR));
+}
+
+pub fn fire(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.light.insert(entity_id, Light::new(FIRE_RADIUS, FIRE_COLOUR));
+    change.tile.insert(entity_id, ComplexTile::Simple(TileType::Fire));
+    change.tile_priority.insert(entity_id, 2);
+    change.fire.insert(entity_id);
 }
 
 pub fn page(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
@@ -116,16 +187,58 @@ pub fn page(change: &mut EntityStoreChange, entity_id: EntityId, position: Vecto
     change.page.insert(entity_id);
 }
 
-pub fn water<R: Rng>(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, rng: &mut R) {
+pub fn shallow_water<R: Rng>(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, rng: &mut R) {
+    water(change, entity_id, position, WaterDepth::Shallow, rng);
+}
+
+pub fn deep_water<R: Rng>(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>, rng: &mut R) {
+    water(change, entity_id, position, WaterDepth::Deep, rng);
+}
+
+fn water<R: Rng>(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>,
+                 depth: WaterDepth, rng: &mut R) {
     change.position.insert(entity_id, position);
     change.water.insert(entity_id);
+    let (tile, alternate_tile) = depth.tiles();
     if rng.gen::<f64>() < WATER_PROBABILITY {
-        change.tile.insert(entity_id, ComplexTile::Simple(TileType::Water1));
+        change.tile.insert(entity_id, ComplexTile::Simple(tile));
     } else {
-        change.tile.insert(entity_id, ComplexTile::Simple(TileType::Water2));
+        change.tile.insert(entity_id, ComplexTile::Simple(alternate_tile));
     }
     change.tile_priority.insert(entity_id, 2);
     change.veil_slot.insert(entity_id);
-    change.solid.insert(entity_id);
     change.low_tile.insert(entity_id);
+    if depth == WaterDepth::Deep {
+        change.deep_water.insert(entity_id);
+    }
+}
+
+pub fn tall_grass(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.tile.insert(entity_id, ComplexTile::Simple(TileType::TallGrass));
+    change.tile_priority.insert(entity_id, 2);
+    change.opacity.insert(entity_id, TALL_GRASS_OPACITY);
+}
+
+pub fn cracks(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.tile.insert(entity_id, ComplexTile::Simple(TileType::Cracks));
+    change.tile_priority.insert(entity_id, 3);
+    change.cracks.insert(entity_id);
+}
+
+pub fn smoke(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.tile.insert(entity_id, ComplexTile::Simple(TileType::Smoke));
+    change.tile_priority.insert(entity_id, 3);
+    change.opacity.insert(entity_id, SMOKE_INITIAL_OPACITY);
+    change.smoke.insert(entity_id);
+}
+
+pub fn heavy_rain(change: &mut EntityStoreChange, entity_id: EntityId, position: Vector2<i32>) {
+    change.position.insert(entity_id, position);
+    change.tile.insert(entity_id, ComplexTile::Simple(TileType::HeavyRain));
+    change.tile_priority.insert(entity_id, 3);
+    change.opacity.insert(entity_id, HEAVY_RAIN_MAX_OPACITY);
+    change.heavy_rain.insert(entity_id);
 }
@@ -1 +1,26 @@
 pub const RAIN_FRAME_RATE: u64 = 6;
+
+pub const INITIAL_RAIN_INTENSITY: f64 = 0.5;
+
+// number of cells a rain drop falls
+pub const RAIN_LENGTH: i32 = 8;
+
+// rain drops per cell when the weather is at full intensity
+pub const MAX_RAIN_DENSITY: f64 = 0.2;
+
+// maximum number of rain drops spawned or retired each turn
+pub const RAIN_CHANGE_PER_TURN: usize = 20;
+
+// how quickly the weather moves towards its target each turn
+pub const WEATHER_INTENSITY_STEP: f64 = 0.01;
+pub const WIND_STEP: f64 = 0.02;
+
+// cells a rain drop is blown sideways for each cell it falls
+pub const MAX_WIND: f64 = 1.0;
+
+// above this intensity, entities outside can't see as far
+pub const HEAVY_RAIN_INTENSITY: f64 = 0.7;
+pub const HEAVY_RAIN_VISION_DISTANCE: u32 = 8;
+
+// amount by which noises are quietened at full intensity
+pub const MAX_RAIN_NOISE_MUFFLING: u32 = 6;
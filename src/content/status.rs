@@ -0,0 +1,116 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use std::collections::btree_map;
+
+pub const SLOWED_TURN_PENALTY: u64 = 1;
+pub const BLINDED_VISION_DISTANCE: u32 = 1;
+pub const VEIL_TOUCHED_VISION_DISTANCE: u32 = 6;
+pub const VEIL_TOUCHED_TURN_PENALTY: u64 = 1;
+pub const BURNING_DAMAGE: u32 = 1;
+
+// turns the veil lingers on an entity after it leaves
+pub const VEIL_TOUCHED_DURATION: u64 = 8;
+// turns an entity is slowed after swimming
+pub const SOAKED_DURATION: u64 = 4;
+// turns an entity keeps burning after leaving a fire
+pub const BURNING_DURATION: u64 = 6;
+// turns an npc is dazzled after a weapon is fired right next to it
+pub const BLINDED_DURATION: u64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum StatusEffect {
+    Slowed,
+    Blinded,
+    VeilTouched,
+    Burning,
+}
+
+impl StatusEffect {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            StatusEffect::Slowed => "Slowed",
+            StatusEffect::Blinded => "Blinded",
+            StatusEffect::VeilTouched => "Veil-touched",
+            StatusEffect::Burning => "Burning",
+        }
+    }
+
+    // extra time taken by each turn
+    pub fn turn_penalty(self) -> u64 {
+        match self {
+            StatusEffect::Slowed => SLOWED_TURN_PENALTY,
+            StatusEffect::VeilTouched => VEIL_TOUCHED_TURN_PENALTY,
+            _ => 0,
+        }
+    }
+
+    // furthest distance that can be seen
+    pub fn vision_limit(self) -> Option<u32> {
+        match self {
+            StatusEffect::Blinded => Some(BLINDED_VISION_DISTANCE),
+            StatusEffect::VeilTouched => Some(VEIL_TOUCHED_VISION_DISTANCE),
+            _ => None,
+        }
+    }
+
+    // damage taken at the start of each turn
+    pub fn damage_per_turn(self) -> u32 {
+        match self {
+            StatusEffect::Burning => BURNING_DAMAGE,
+            _ => 0,
+        }
+    }
+}
+
+// The status effects on an entity, with the time on the turn schedule
+// at which each wears off. The base properties of the entity are left
+// alone, and the effects are combined with them where they're used, so
+// there's nothing to undo when an effect is removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffects {
+    effects: BTreeMap<StatusEffect, u64>,
+}
+
+impl StatusEffects {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    pub fn contains(&self, effect: StatusEffect) -> bool {
+        self.effects.contains_key(&effect)
+    }
+
+    pub fn expiry(&self, effect: StatusEffect) -> Option<u64> {
+        self.effects.get(&effect).cloned()
+    }
+
+    // Adds an effect, or extends it if it's already present.
+    pub fn insert(&mut self, effect: StatusEffect, expiry: u64) {
+        let expiry = cmp::max(expiry, self.expiry(effect).unwrap_or(0));
+        self.effects.insert(effect, expiry);
+    }
+
+    pub fn remove(&mut self, effect: StatusEffect) -> bool {
+        self.effects.remove(&effect).is_some()
+    }
+
+    pub fn iter(&self) -> btree_map::Keys<StatusEffect, u64> {
+        self.effects.keys()
+    }
+
+    pub fn turn_penalty(&self) -> u64 {
+        self.iter().map(|effect| effect.turn_penalty()).sum()
+    }
+
+    pub fn vision_limit(&self) -> Option<u32> {
+        self.iter().filter_map(|effect| effect.vision_limit()).min()
+    }
+
+    pub fn damage_per_turn(&self) -> u32 {
+        self.iter().map(|effect| effect.damage_per_turn()).sum()
+    }
+}
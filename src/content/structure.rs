@@ -0,0 +1,7 @@
+// damage a structure can take from projectiles before it's destroyed
+pub const WALL_INTEGRITY: u32 = 4;
+pub const STONE_WALL_INTEGRITY: u32 = 8;
+pub const DOOR_INTEGRITY: u32 = 2;
+
+// structures this close to being destroyed are visibly cracked
+pub const CRACKED_INTEGRITY: u32 = 2;
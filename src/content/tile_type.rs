@@ -22,6 +22,21 @@ pub enum TileType {
     WoodenPost,
     StoneWallFront,
     StoneWallTop,
+    TallGrass,
+    Smoke,
+    HeavyRain,
+    EnemyGhost,
+    EnemyGhostFading,
+    EnemyGhostFaint,
+    ShallowWater1,
+    ShallowWater2,
+    Submerged,
+    Key,
+    Rubble,
+    Cracks,
+    Bandage,
+    AmmoBox,
+    Fire,
 }
 }
 
@@ -49,8 +64,23 @@ impl TileType {
             TileType::WoodenPost => "WoodenPost",
             TileType::StoneWallFront => "StoneWallFront",
             TileType::StoneWallTop => "StoneWallTop",
+            TileType::TallGrass => "TallGrass",
+            TileType::Smoke => "Smoke",
+            TileType::HeavyRain => "HeavyRain",
+            TileType::EnemyGhost => "EnemyGhost",
+            TileType::EnemyGhostFading => "EnemyGhostFading",
+            TileType::EnemyGhostFaint => "EnemyGhostFaint",
+            TileType::ShallowWater1 => "ShallowWater1",
+            TileType::ShallowWater2 => "ShallowWater2",
+            TileType::Submerged => "Submerged",
+            TileType::Key => "Key",
+            TileType::Rubble => "Rubble",
+            TileType::Cracks => "Cracks",
+            TileType::Bandage => "Bandage",
+            TileType::AmmoBox => "AmmoBox",
+            TileType::Fire => "Fire",
         }
     }
 }
 
-pub const NUM_TILES: usize = 21;
+pub const NUM_TILES: usize = 36;
@@ -1,3 +1,44 @@
+use spatial_hash::SpatialHashCell;
+use content::TileType;
+
 pub const WATER_PROBABILITY: f64 = 0.2;
 pub const WATER_FRAME_RATE: u64 = 4;
 pub const WATER_TILE_CHANGE_PROBABILITY: f64 = 0.1;
+
+// extra time taken by a turn which ends in water
+pub const SHALLOW_WATER_TURN_COST: u64 = 1;
+pub const DEEP_WATER_TURN_COST: u64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WaterDepth {
+    Shallow,
+    Deep,
+}
+
+impl WaterDepth {
+    // the depth of water in a cell, if it has any
+    pub fn of_cell(cell: &SpatialHashCell) -> Option<Self> {
+        if cell.deep_water_count > 0 {
+            Some(WaterDepth::Deep)
+        } else if cell.water_count > 0 {
+            Some(WaterDepth::Shallow)
+        } else {
+            None
+        }
+    }
+
+    // the two tiles alternated between to animate the water
+    pub fn tiles(self) -> (TileType, TileType) {
+        match self {
+            WaterDepth::Shallow => (TileType::ShallowWater1, TileType::ShallowWater2),
+            WaterDepth::Deep => (TileType::Water1, TileType::Water2),
+        }
+    }
+
+    pub fn turn_cost(self) -> u64 {
+        match self {
+            WaterDepth::Shallow => SHALLOW_WATER_TURN_COST,
+            WaterDepth::Deep => DEEP_WATER_TURN_COST,
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::cmp;
+use std::f64::consts::PI;
+use content::ProjectileType;
+use straight_line::InfiniteAbsoluteLineTraverse;
+use cgmath::Vector2;
+
+// angle between neighbouring pellets of a spread
+pub const SPREAD_ANGLE: f64 = PI / 24.0;
+
+// length of each spread line's delta, so rounding doesn't
+// collapse neighbouring pellets onto the same line
+const SPREAD_PRECISION: f64 = 32.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WeaponType {
+    Pistol,
+    Shotgun,
+    Rifle,
+}
+
+impl WeaponType {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            WeaponType::Pistol => "Pistol",
+            WeaponType::Shotgun => "Shotgun",
+            WeaponType::Rifle => "Rifle",
+        }
+    }
+
+    pub fn projectile_type(self) -> ProjectileType {
+        match self {
+            WeaponType::Pistol => ProjectileType::Bullet,
+            WeaponType::Shotgun => ProjectileType::Pellet,
+            WeaponType::Rifle => ProjectileType::RifleRound,
+        }
+    }
+
+    // rounds held when fully loaded
+    pub fn capacity(self) -> u32 {
+        match self {
+            WeaponType::Pistol => 6,
+            WeaponType::Shotgun => 2,
+            WeaponType::Rifle => 4,
+        }
+    }
+
+    // projectiles fired with each round
+    pub fn pellets(self) -> usize {
+        match self {
+            WeaponType::Pistol => 1,
+            WeaponType::Shotgun => 5,
+            WeaponType::Rifle => 1,
+        }
+    }
+
+    // extra time before the next turn after firing
+    pub fn fire_time(self) -> u64 {
+        match self {
+            WeaponType::Pistol => 0,
+            WeaponType::Shotgun => 0,
+            WeaponType::Rifle => 2,
+        }
+    }
+
+    // extra time before the next turn after reloading
+    pub fn reload_time(self) -> u64 {
+        match self {
+            WeaponType::Pistol => 1,
+            WeaponType::Shotgun => 2,
+            WeaponType::Rifle => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Weapon {
+    pub weapon_type: WeaponType,
+    pub loaded: u32,
+    // spare rounds carried for reloading
+    pub reserve: u32,
+}
+
+impl Weapon {
+    // a fully loaded weapon
+    pub fn new(weapon_type: WeaponType, reserve: u32) -> Self {
+        Weapon {
+            weapon_type: weapon_type,
+            loaded: weapon_type.capacity(),
+            reserve: reserve,
+        }
+    }
+
+    pub fn can_fire(&self) -> bool {
+        self.loaded > 0
+    }
+
+    pub fn can_reload(&self) -> bool {
+        self.loaded < self.weapon_type.capacity() && self.reserve > 0
+    }
+
+    // Uses up a round, returning false if there was none loaded.
+    pub fn fire(&mut self) -> bool {
+        if self.can_fire() {
+            self.loaded -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Fills the weapon from the reserve, returning the number of rounds loaded.
+    pub fn reload(&mut self) -> u32 {
+        let count = cmp::min(self.weapon_type.capacity() - self.loaded, self.reserve);
+        self.loaded += count;
+        self.reserve -= count;
+        count
+    }
+}
+
+// The weapons carried by an entity, one of which is equipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Arsenal {
+    weapons: Vec<Weapon>,
+    equipped: usize,
+}
+
+impl Arsenal {
+    pub fn new(weapons: Vec<Weapon>) -> Self {
+        Arsenal {
+            weapons: weapons,
+            equipped: 0,
+        }
+    }
+
+    pub fn equipped(&self) -> Option<&Weapon> {
+        self.weapons.get(self.equipped)
+    }
+
+    pub fn equipped_mut(&mut self) -> Option<&mut Weapon> {
+        self.weapons.get_mut(self.equipped)
+    }
+
+    pub fn weapon_mut(&mut self, weapon_type: WeaponType) -> Option<&mut Weapon> {
+        self.weapons.iter_mut().find(|weapon| weapon.weapon_type == weapon_type)
+    }
+
+    // Equips the next weapon, wrapping around to the first.
+    pub fn cycle(&mut self) {
+        if !self.weapons.is_empty() {
+            self.equipped = (self.equipped + 1) % self.weapons.len();
+        }
+    }
+}
+
+// Lines from the start of the given line, fanned evenly around it.
+pub fn spread(traverse: InfiniteAbsoluteLineTraverse, count: usize) -> Vec<InfiniteAbsoluteLineTraverse> {
+    if count <= 1 {
+        return vec![traverse];
+    }
+
+    let start = traverse.current();
+    let delta = traverse.delta();
+    let aim = (delta.y as f64).atan2(delta.x as f64);
+    let middle = (count - 1) as f64 / 2.0;
+
+    (0..count).map(|i| {
+        let angle = aim + (i as f64 - middle) * SPREAD_ANGLE;
+        let delta = Vector2::new((angle.cos() * SPREAD_PRECISION).round() as i32,
+                                 (angle.sin() * SPREAD_PRECISION).round() as i32);
+        InfiniteAbsoluteLineTraverse::new_offset(start, delta)
+    }).collect()
+}
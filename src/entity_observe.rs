@@ -2,7 +2,9 @@ use std::result;
 use entity_store::{EntityId, EntityStore};
 use spatial_hash::SpatialHashTable;
 use knowledge::KnowledgeGrid;
-use observation::{shadowcast, omniscient, ObservationMetadata};
+use observation::{omniscient, ObservationMetadata, FovCacheTable, FovEnv};
+use light_grid::{LightGrid, LitKnowledgeGrid};
+use content::{FovType, LIT_VISION_MULTIPLIER};
 
 #[derive(Debug)]
 pub enum Error {
@@ -17,23 +19,47 @@ pub fn entity_observe<K: KnowledgeGrid>(
     spatial_hash: &SpatialHashTable,
     time: u64,
     knowledge: &mut K,
-    shadowcast: &mut shadowcast::ShadowcastEnv) -> Result<ObservationMetadata> {
+    fov: &mut FovEnv,
+    fov_cache: &mut FovCacheTable,
+    light_grid: &mut LightGrid) -> Result<ObservationMetadata> {
 
     let position = entity_store.position.get(&id).ok_or(Error::MissingPosition)?;
-    let vision_distance = entity_store.vision_distance.get(&id).ok_or(Error::MissingVisionDistance)?;
+    let mut vision_distance = *entity_store.vision_distance.get(&id).ok_or(Error::MissingVisionDistance)?;
+    if let Some(blurred_vision) = entity_store.blurred_vision.get(&id) {
+        vision_distance = vision_distance.min(*blurred_vision);
+    }
+    if let Some(weather_vision) = entity_store.weather_vision.get(&id) {
+        vision_distance = vision_distance.min(*weather_vision);
+    }
+    if let Some(limit) = entity_store.status_effects.get(&id).and_then(|effects| effects.vision_limit()) {
+        vision_distance = vision_distance.min(limit);
+    }
 
-    if entity_store.omniscient.contains(&id) {
+    let fov_type = entity_store.fov_type.get(&id).cloned().unwrap_or(FovType::Shadowcast);
+
+    if entity_store.omniscient.contains(&id) || fov_type == FovType::Omniscient {
         return Ok(omniscient::observe(spatial_hash, entity_store, time, knowledge));
     }
 
-    Ok(shadowcast::observe(
-        shadowcast,
+    light_grid.update(&mut fov.shadowcast, spatial_hash, entity_store, time);
+
+    let mut lit_knowledge = LitKnowledgeGrid {
+        knowledge: knowledge,
+        light_grid: light_grid,
+        eye: *position,
+        vision_distance: vision_distance,
+    };
+
+    Ok(fov_cache.observe(
+        id,
+        fov,
+        fov_type,
         *position,
         spatial_hash,
-        *vision_distance,
+        vision_distance * LIT_VISION_MULTIPLIER,
         entity_store,
         time,
-        knowledge
+        &mut lit_knowledge
     ))
 }
 
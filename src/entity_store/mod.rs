@@ -66,6 +66,9 @@ impl<T> DataComponentChange<T> {
     pub fn cancel(&mut self, id: EntityId) -> Option<DataChangeType<T>> {
         self.0.remove(&id)
     }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 impl FlagComponentChange {
     pub fn iter(&self) -> FlagComponentChangeIter {
@@ -80,6 +83,9 @@ impl FlagComponentChange {
     pub fn cancel(&mut self, id: EntityId) -> Option<FlagChangeType> {
         self.0.remove(&id)
     }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 entity_store_change_decl!{EntityStoreChange}
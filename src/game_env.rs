@@ -4,8 +4,9 @@ use entity_id_allocator::EntityIdAllocator;
 use schedule::{Schedule, ScheduleEntry};
 use content::ActionType;
 use policy::GamePolicy;
-use observation::shadowcast::ShadowcastEnv;
+use observation::FovEnv;
 use reaction::Reaction;
+use projectile_config::ProjectileConfig;
 
 pub struct GameEnv {
     pub id_allocator: EntityIdAllocator,
@@ -13,7 +14,8 @@ pub struct GameEnv {
     pub rng: StdRng,
     pub action_schedule: Schedule<ActionType>,
     pub policy: GamePolicy,
-    pub shadowcast: ShadowcastEnv,
+    pub projectile_config: ProjectileConfig,
+    pub fov: FovEnv,
     pub reactions: Vec<Reaction>,
     pub action_schedule_entries: Vec<ScheduleEntry<ActionType>>,
     pub time: u64,
@@ -27,7 +29,8 @@ impl GameEnv {
             rng: StdRng::new().expect("Failed to init rng"),
             action_schedule: Schedule::new(),
             policy: GamePolicy::new(),
-            shadowcast: ShadowcastEnv::new(),
+            projectile_config: ProjectileConfig::load(),
+            fov: FovEnv::new(),
             reactions: Vec::new(),
             action_schedule_entries: Vec::new(),
             time: 1,
@@ -1,47 +1,62 @@
+use std::collections::VecDeque;
 use gfx;
 use gfx::Device;
 use gfx_window_glutin;
 use glutin;
 use gfx_device_gl;
+use gfx_text;
 
 use glutin_frontend::input;
 use glutin_frontend::overlay_tile::{self, OverlayCoord};
 use glutin_frontend::formats::{ColourFormat, DepthFormat};
 use glutin_frontend::tile_map::UpdateTileMapData;
-use glutin_frontend::sizes::{WIDTH_TILES, HEIGHT_TILES, TILE_SIZE};
+use glutin_frontend::sizes::ViewportConfig;
 use glutin_frontend::renderer_pipelines::RendererPipelines;
+use glutin_frontend::types::DepthStencilView;
 
 use cgmath::Vector2;
 
 use knowledge::PlayerKnowledgeGrid;
 use renderer::{GameRenderer, GameRendererConfig};
 use render_overlay::RenderOverlay;
+use hud::{Hud, InventoryEntry, InventoryPrompt};
 use input::{GameInput, InputEvent, ExternalEvent};
 use frame::Frame;
 use common_input::CommonInput;
 use tile_buffer::TileBuffer;
 use tile::TileResolver;
 use grid::static_grid::StaticGridIdx;
-use content::OverlayType;
+use content::{OverlayType, Message};
 
 const FPS: u32 = 60;
 
-const WIDTH_PX: u32 = WIDTH_TILES * TILE_SIZE;
-const HEIGHT_PX: u32 = HEIGHT_TILES * TILE_SIZE;
-const NUM_TILES: u32 = WIDTH_TILES * HEIGHT_TILES;
-
 const CLEAR_COLOUR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
 
+const TEXT_SIZE: u8 = 16;
+const TEXT_LINE_HEIGHT: i32 = 20;
+const TEXT_MARGIN: i32 = 8;
+const TEXT_COLOUR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+// the most recent messages are shown at the bottom of the window
+const MAX_MESSAGES: usize = 4;
+
 pub struct GlutinGameRenderer {
     encoder: gfx::Encoder<gfx_device_gl::Resources, gfx_device_gl::CommandBuffer>,
     window: glutin::Window,
     device: gfx_device_gl::Device,
+    factory: gfx_device_gl::Factory,
+    depth: DepthStencilView,
     pipelines: RendererPipelines,
     tile_buffer: TileBuffer,
     tile_resolver: TileResolver,
     player_coord: Vector2<i32>,
     offset_delta: Vector2<i32>,
     config: GameRendererConfig,
+    viewport: ViewportConfig,
+    text: gfx_text::Renderer<gfx_device_gl::Resources, gfx_device_gl::Factory>,
+    hud: Option<Hud>,
+    inventory: Option<(InventoryPrompt, Vec<InventoryEntry>)>,
+    messages: VecDeque<Message>,
 }
 
 pub struct GlutinGameInput {
@@ -49,34 +64,47 @@ pub struct GlutinGameInput {
     common_input: CommonInput,
 }
 
-pub fn create() -> (GlutinGameRenderer, GlutinGameInput) {
+fn offset_delta(viewport: ViewportConfig) -> Vector2<i32> {
+    Vector2::new(viewport.width_tiles as i32 / 2, viewport.height_tiles as i32 / 2)
+}
+
+pub fn create(viewport: ViewportConfig) -> (GlutinGameRenderer, GlutinGameInput) {
 
     let builder = glutin::WindowBuilder::new()
         .with_decorations(true)
-        .with_dimensions(WIDTH_PX, HEIGHT_PX)
-        .with_min_dimensions(WIDTH_PX, HEIGHT_PX)
-        .with_max_dimensions(WIDTH_PX, HEIGHT_PX)
+        .with_dimensions(viewport.width_px(), viewport.height_px())
+        .with_min_dimensions(viewport.tile_size, viewport.tile_size)
         .with_title("Veil".to_string());
 
     let events_loop = glutin::EventsLoop::new();
 
-    let (window, device, mut factory, rtv, _) =
+    let (window, device, mut factory, rtv, depth) =
         gfx_window_glutin::init::<ColourFormat, DepthFormat>(builder, &events_loop);
 
     let mut encoder: gfx::Encoder<_, _> = factory.create_command_buffer().into();
 
-    let pipelines = RendererPipelines::new(WIDTH_TILES, HEIGHT_TILES, rtv, &mut factory, &mut encoder);
+    let pipelines = RendererPipelines::new(viewport.width_tiles, viewport.height_tiles, rtv, &mut factory, &mut encoder);
+
+    let text = gfx_text::new(factory.clone()).with_size(TEXT_SIZE).build()
+        .expect("Failed to create text renderer");
 
     let renderer = GlutinGameRenderer {
         encoder: encoder,
         window: window,
         device: device,
+        factory: factory,
+        depth: depth,
         tile_resolver: TileResolver::from_desc(&pipelines.description),
         pipelines: pipelines,
-        tile_buffer: TileBuffer::new(WIDTH_TILES as usize, HEIGHT_TILES as usize),
+        tile_buffer: TileBuffer::new(viewport.width_tiles as usize, viewport.height_tiles as usize),
         player_coord: Vector2::new(0, 0),
-        offset_delta: Vector2::new(WIDTH_TILES as i32 / 2, HEIGHT_TILES as i32 / 2),
+        offset_delta: offset_delta(viewport),
         config: Default::default(),
+        viewport: viewport,
+        text: text,
+        hud: None,
+        inventory: None,
+        messages: VecDeque::new(),
     };
 
     let input = GlutinGameInput {
@@ -87,6 +115,64 @@ pub fn create() -> (GlutinGameRenderer, GlutinGameInput) {
     (renderer, input)
 }
 
+impl GlutinGameRenderer {
+    fn set_viewport(&mut self, viewport: ViewportConfig) {
+        if viewport == self.viewport {
+            return;
+        }
+
+        let rtv = self.pipelines.scale.data.out.clone();
+
+        self.pipelines.resize(viewport.width_tiles, viewport.height_tiles, rtv, &mut self.factory, &mut self.encoder);
+        self.tile_buffer = TileBuffer::new(viewport.width_tiles as usize, viewport.height_tiles as usize);
+        self.offset_delta = offset_delta(viewport);
+        self.viewport = viewport;
+    }
+
+    fn window_size_px(&self) -> (u32, u32) {
+        self.window.get_inner_size_pixels()
+            .unwrap_or((self.viewport.width_px(), self.viewport.height_px()))
+    }
+
+    // Text is drawn straight onto the window, after the map has been
+    // scaled onto it, so it stays the same size at any zoom level.
+    fn draw_text(&mut self) {
+        let mut y = TEXT_MARGIN;
+
+        if let Some(ref hud) = self.hud {
+            for line in hud.lines() {
+                self.text.add(&line, [TEXT_MARGIN, y], TEXT_COLOUR);
+                y += TEXT_LINE_HEIGHT;
+            }
+        }
+
+        if let Some((prompt, ref entries)) = self.inventory {
+            y += TEXT_LINE_HEIGHT;
+            self.text.add(prompt.to_str(), [TEXT_MARGIN, y], TEXT_COLOUR);
+            y += TEXT_LINE_HEIGHT;
+
+            if entries.is_empty() {
+                self.text.add("You aren't carrying anything.", [TEXT_MARGIN, y], TEXT_COLOUR);
+            }
+
+            for entry in entries.iter() {
+                self.text.add(&entry.text(), [TEXT_MARGIN, y], TEXT_COLOUR);
+                y += TEXT_LINE_HEIGHT;
+            }
+        }
+
+        let (_, height_px) = self.window_size_px();
+        let mut y = height_px as i32 - TEXT_MARGIN - TEXT_LINE_HEIGHT * self.messages.len() as i32;
+        for message in self.messages.iter() {
+            self.text.add(message.to_str(), [TEXT_MARGIN, y], TEXT_COLOUR);
+            y += TEXT_LINE_HEIGHT;
+        }
+
+        self.text.draw(&mut self.encoder, &self.pipelines.scale.data.out)
+            .expect("Failed to draw text");
+    }
+}
+
 impl GameRenderer for GlutinGameRenderer {
     fn clear(&mut self) {
         self.encoder.clear(&self.pipelines.scale.data.out, CLEAR_COLOUR);
@@ -97,7 +183,8 @@ impl GameRenderer for GlutinGameRenderer {
     }
 
     fn update_player_knowledge(&mut self, knowledge: &PlayerKnowledgeGrid, time: u64) {
-        self.tile_buffer.update(self.player_coord - self.offset_delta, knowledge, &self.tile_resolver, time);
+        self.tile_buffer.update(self.player_coord - self.offset_delta, knowledge, &self.tile_resolver, time,
+                               self.config.veil_forecast);
 
         self.pipelines.world.update_tile_map_data(self.tile_buffer.iter());
         self.pipelines.world.update_buffer(&mut self.encoder);
@@ -140,7 +227,27 @@ impl GameRenderer for GlutinGameRenderer {
         self.encoder.clear(&self.pipelines.overlay.data.out, [0.0, 0.0, 0.0, 0.0]);
     }
 
+    fn update_hud(&mut self, hud: &Hud) {
+        self.hud = Some(hud.clone());
+    }
+
+    fn show_inventory(&mut self, prompt: InventoryPrompt, entries: &[InventoryEntry]) {
+        self.inventory = Some((prompt, entries.to_vec()));
+    }
+
+    fn hide_inventory(&mut self) {
+        self.inventory = None;
+    }
+
+    fn show_message(&mut self, message: Message) {
+        if self.messages.len() == MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message);
+    }
+
     fn publish(&mut self) {
+        self.draw_text();
         self.encoder.flush(&mut self.device);
         self.window.swap_buffers().expect("Failed to swap buffers");
         self.device.cleanup();
@@ -153,6 +260,18 @@ impl GameRenderer for GlutinGameRenderer {
     fn config(&self) -> GameRendererConfig {
         self.config
     }
+
+    fn resize(&mut self, width_px: u32, height_px: u32) {
+        gfx_window_glutin::update_views(&self.window, &mut self.pipelines.scale.data.out, &mut self.depth);
+        let viewport = self.viewport.fit(width_px, height_px);
+        self.set_viewport(viewport);
+    }
+
+    fn zoom(&mut self, steps: i32) {
+        let (width_px, height_px) = self.window_size_px();
+        let viewport = self.viewport.zoom(steps, width_px, height_px);
+        self.set_viewport(viewport);
+    }
 }
 
 impl GameInput for GlutinGameInput {
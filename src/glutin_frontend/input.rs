@@ -87,6 +87,8 @@ fn convert_key(keycode: VirtualKeyCode, keymod: ModifiersState) -> Option<InputE
         VirtualKeyCode::Period => to_char_event('.', keymod),
         VirtualKeyCode::Comma => to_char_event(',', keymod),
         VirtualKeyCode::Slash => to_char_event('/', keymod),
+        VirtualKeyCode::Minus => to_char_event('-', keymod),
+        VirtualKeyCode::Equals => to_char_event('=', keymod),
         _ => None,
     }
 }
@@ -100,6 +102,7 @@ pub fn convert_event(event: Event) -> Option<InputEvent> {
 
     match event {
         WindowEvent::Closed => return Some(InputEvent::Quit),
+        WindowEvent::Resized(width, height) => return Some(InputEvent::Resize(width, height)),
         WindowEvent::KeyboardInput { input, .. } => {
             if input.state == ElementState::Pressed {
                 if let Some(keycode) = input.virtual_keycode {
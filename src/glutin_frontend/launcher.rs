@@ -1,7 +1,8 @@
 use glutin_frontend::frontend;
+use glutin_frontend::sizes::ViewportConfig;
 use launch;
 
 pub fn launch() {
-    let (mut renderer, mut input) = frontend::create();
+    let (mut renderer, mut input) = frontend::create(ViewportConfig::load());
     launch::launch(&mut renderer, &mut input);
 }
@@ -1,5 +1,6 @@
 use gfx::Factory;
 use gfx_device_gl;
+use image::RgbaImage;
 
 use glutin_frontend::tile_map::TileMapPipeline;
 use glutin_frontend::scale::ScalePipeline;
@@ -15,6 +16,7 @@ pub struct RendererPipelines {
     pub overlay: TileMapPipeline,
     pub scale: ScalePipeline,
     pub description: TileDesc,
+    tile_img: RgbaImage,
 }
 
 struct ViewPair(ShaderResourceView, RenderTargetView);
@@ -28,6 +30,56 @@ impl ViewPair {
     }
 }
 
+fn create_pipelines(width_tiles: u32,
+                    height_tiles: u32,
+                    tile_img: &RgbaImage,
+                    tile_desc: &TileDesc,
+                    rtv: RenderTargetView,
+                    factory: &mut gfx_device_gl::Factory,
+                    encoder: &mut Encoder) -> (TileMapPipeline, TileMapPipeline, ScalePipeline) {
+
+    let buf_width_px = width_tiles * tile_desc.tile_size;
+    let buf_height_px = height_tiles * tile_desc.tile_size;
+
+    let ViewPair(world_resource, world_target) = ViewPair::new(buf_width_px, buf_height_px, factory);
+    let ViewPair(overlay_resource, overlay_target) = ViewPair::new(buf_width_px, buf_height_px, factory);
+
+    let mut world_pipeline = TileMapPipeline::new(width_tiles, height_tiles, (width_tiles * height_tiles) as usize,
+                                                  include_bytes!("shaders/shdr_330.vert"),
+                                                  include_bytes!("shaders/shdr_world_330.frag"),
+                                                  world_tile::shader_template_info(),
+                                                  tile_img,
+                                                  tile_desc,
+                                                  world_target,
+                                                  factory,
+                                                  encoder);
+
+    world_tile::init_tile_map_data(&mut world_pipeline.buffer);
+
+    let overlay_pipeline = TileMapPipeline::new(width_tiles, height_tiles, (width_tiles * height_tiles) as usize,
+                                                include_bytes!("shaders/shdr_330.vert"),
+                                                include_bytes!("shaders/shdr_overlay_330.frag"),
+                                                overlay_tile::shader_template_info(),
+                                                tile_img,
+                                                tile_desc,
+                                                overlay_target,
+                                                factory,
+                                                encoder);
+
+
+    let scale_pipeline = ScalePipeline::new(include_bytes!("shaders/shdr_scale_330.vert"),
+                                            include_bytes!("shaders/shdr_scale_330.frag"),
+                                            buf_width_px,
+                                            buf_height_px,
+                                            world_resource,
+                                            overlay_resource,
+                                            rtv,
+                                            factory,
+                                            encoder);
+
+    (world_pipeline, overlay_pipeline, scale_pipeline)
+}
+
 impl RendererPipelines {
     pub fn new(width_tiles: u32,
                height_tiles: u32,
@@ -37,49 +89,35 @@ impl RendererPipelines {
 
         let (tile_img, tile_desc) = tile::read_tiles();
 
-        let buf_width_px = width_tiles * tile_desc.tile_size;
-        let buf_height_px = height_tiles * tile_desc.tile_size;
-
-        let ViewPair(world_resource, world_target) = ViewPair::new(buf_width_px, buf_height_px, factory);
-        let ViewPair(overlay_resource, overlay_target) = ViewPair::new(buf_width_px, buf_height_px, factory);
-
-        let mut world_pipeline = TileMapPipeline::new(width_tiles, height_tiles, (width_tiles * height_tiles) as usize,
-                                                      include_bytes!("shaders/shdr_330.vert"),
-                                                      include_bytes!("shaders/shdr_world_330.frag"),
-                                                      world_tile::shader_template_info(),
-                                                      &tile_img,
-                                                      &tile_desc,
-                                                      world_target,
-                                                      factory,
-                                                      encoder);
-
-        world_tile::init_tile_map_data(&mut world_pipeline.buffer);
-
-        let overlay_pipeline = TileMapPipeline::new(height_tiles, height_tiles, (height_tiles * height_tiles) as usize,
-                                                    include_bytes!("shaders/shdr_330.vert"),
-                                                    include_bytes!("shaders/shdr_overlay_330.frag"),
-                                                    overlay_tile::shader_template_info(),
-                                                    &tile_img,
-                                                    &tile_desc,
-                                                    overlay_target,
-                                                    factory,
-                                                    encoder);
-
-
-        let scale_pipeline = ScalePipeline::new(include_bytes!("shaders/shdr_scale_330.vert"),
-                                                include_bytes!("shaders/shdr_scale_330.frag"),
-                                                buf_width_px,
-                                                buf_height_px,
-                                                world_resource,
-                                                overlay_resource,
-                                                rtv,
-                                                factory,
-                                                encoder);
+        let (world, overlay, scale) = create_pipelines(width_tiles, height_tiles,
+                                                       &tile_img, &tile_desc,
+                                                       rtv, factory, encoder);
+
         RendererPipelines {
-            world: world_pipeline,
-            overlay: overlay_pipeline,
-            scale: scale_pipeline,
+            world: world,
+            overlay: overlay,
+            scale: scale,
             description: tile_desc,
+            tile_img: tile_img,
         }
     }
+
+    // Rebuilds all pipelines for a tile map of the given dimensions.
+    // The shaders are instantiated with the tile map dimensions, so
+    // this recompiles them.
+    pub fn resize(&mut self,
+                  width_tiles: u32,
+                  height_tiles: u32,
+                  rtv: RenderTargetView,
+                  factory: &mut gfx_device_gl::Factory,
+                  encoder: &mut Encoder) {
+
+        let (world, overlay, scale) = create_pipelines(width_tiles, height_tiles,
+                                                       &self.tile_img, &self.description,
+                                                       rtv, factory, encoder);
+
+        self.world = world;
+        self.overlay = overlay;
+        self.scale = scale;
+    }
 }
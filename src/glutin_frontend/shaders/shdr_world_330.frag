@@ -11,6 +11,10 @@ const int TILE_STATUS_VISIBLE = {{TILE_STATUS_VISIBLE}};
 const int STATUS_BITS_PER_CHANNEL = {{STATUS_BITS_PER_CHANNEL}};
 const int CHANNEL_PRESENT_OFFSET = {{CHANNEL_PRESENT_OFFSET}};
 const int CHANNEL_DIMINISH_OFFSET = {{CHANNEL_DIMINISH_OFFSET}};
+const int TILE_LIGHT_IDX = {{TILE_LIGHT_IDX}};
+const int TILE_LIGHT_SHIFT = {{TILE_LIGHT_SHIFT}};
+const int TILE_STALENESS_SHIFT = {{TILE_STALENESS_SHIFT}};
+const int TILE_STALENESS_MAX = {{TILE_STALENESS_MAX}};
 
 in vec2 v_CellPos;
 
@@ -107,6 +111,17 @@ vec4 resolve_visible(vec4 data, int status) {
     return current;
 }
 
+// visible cells are never drawn darker than this
+const float LIGHT_MIN = 0.25;
+
+vec3 resolve_light(vec4 data) {
+    int packed = (floatBitsToInt(data[TILE_LIGHT_IDX]) >> TILE_LIGHT_SHIFT) & 0xffff;
+    float r = float((packed >> 11) & 0x1f) / 31.0;
+    float g = float((packed >> 5) & 0x3f) / 63.0;
+    float b = float(packed & 0x1f) / 31.0;
+    return max(vec3(r, g, b), vec3(LIGHT_MIN));
+}
+
 const float REMEMBERED_DARKEN = 0.2;
 
 float darken(float x, float coef) {
@@ -120,9 +135,22 @@ vec4 darken_colour(vec4 colour, float coef) {
     return vec4(r, g, b, colour[3]);
 }
 
+// the most stale memories are fully grey and this much darker
+const float STALE_FADE = 0.5;
+
+float resolve_staleness(int status) {
+    return float((status >> TILE_STALENESS_SHIFT) & TILE_STALENESS_MAX) / float(TILE_STALENESS_MAX);
+}
+
+vec4 desaturate(vec4 colour, float amount) {
+    float grey = dot(vec3(colour), vec3(0.299, 0.587, 0.114));
+    return vec4(mix(vec3(colour), vec3(grey), amount), colour[3]);
+}
+
 vec4 resolve_remembered(vec4 data, int status) {
-    vec4 coloured = resolve_visible(data, status);
-    return darken_colour(coloured, REMEMBERED_DARKEN);
+    float staleness = resolve_staleness(status);
+    vec4 coloured = desaturate(resolve_visible(data, status), staleness);
+    return darken_colour(coloured, REMEMBERED_DARKEN * (1.0 - STALE_FADE * staleness));
 }
 
 void main() {
@@ -136,7 +164,8 @@ void main() {
     int status = floatBitsToInt(cell_info[TILE_STATUS_IDX]);
 
     if ((status & TILE_STATUS_VISIBLE) != 0) {
-        Target0 = resolve_visible(cell_info, status);
+        vec4 colour = resolve_visible(cell_info, status);
+        Target0 = vec4(vec3(colour) * resolve_light(cell_info), colour[3]);
     } else {
         Target0 = resolve_remembered(cell_info, status);
     }
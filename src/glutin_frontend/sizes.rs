@@ -1,3 +1,70 @@
-pub const WIDTH_TILES: u32 = 15;
-pub const HEIGHT_TILES: u32 = 15;
-pub const TILE_SIZE: u32 = 56;
+use std::cmp;
+use resources::{self, VIEWPORT_SPEC};
+use simple_file;
+
+pub const MIN_TILE_SIZE: u32 = 16;
+pub const MAX_TILE_SIZE: u32 = 128;
+pub const TILE_SIZE_STEP: u32 = 8;
+
+// The tile map is stored in a uniform block, which is only guaranteed
+// to hold 16KB. Each cell is a vec4 of 32 bit values.
+pub const MAX_NUM_TILES: u32 = 16384 / 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewportConfig {
+    pub width_tiles: u32,
+    pub height_tiles: u32,
+    pub tile_size: u32,
+}
+
+impl ViewportConfig {
+    pub fn load() -> Self {
+        simple_file::read_toml::<_, ViewportConfig>(resources::res_path(VIEWPORT_SPEC))
+            .map(ViewportConfig::normalise)
+            .expect("Failed to read viewport spec")
+    }
+
+    pub fn width_px(&self) -> u32 { self.width_tiles * self.tile_size }
+    pub fn height_px(&self) -> u32 { self.height_tiles * self.tile_size }
+    pub fn num_tiles(&self) -> u32 { self.width_tiles * self.height_tiles }
+
+    fn normalise(mut self) -> Self {
+        self.tile_size = cmp::max(MIN_TILE_SIZE, cmp::min(MAX_TILE_SIZE, self.tile_size));
+        self.width_tiles = cmp::max(1, self.width_tiles);
+        self.height_tiles = cmp::max(1, self.height_tiles);
+
+        // shrink the larger dimension until the tile map fits
+        while self.num_tiles() > MAX_NUM_TILES {
+            if self.width_tiles > self.height_tiles {
+                self.width_tiles -= 1;
+            } else {
+                self.height_tiles -= 1;
+            }
+        }
+
+        self
+    }
+
+    // Returns the config with as many tiles as will fit in a
+    // window of the given pixel dimensions.
+    pub fn fit(self, width_px: u32, height_px: u32) -> Self {
+        ViewportConfig {
+            width_tiles: width_px / self.tile_size,
+            height_tiles: height_px / self.tile_size,
+            tile_size: self.tile_size,
+        }.normalise()
+    }
+
+    // Returns the config with the tile size changed by the given
+    // number of steps, refitted to a window of the given pixel dimensions.
+    pub fn zoom(self, steps: i32, width_px: u32, height_px: u32) -> Self {
+        let tile_size = self.tile_size as i32 + steps * TILE_SIZE_STEP as i32;
+        let tile_size = cmp::max(MIN_TILE_SIZE as i32, cmp::min(MAX_TILE_SIZE as i32, tile_size)) as u32;
+
+        ViewportConfig {
+            width_tiles: self.width_tiles,
+            height_tiles: self.height_tiles,
+            tile_size: tile_size,
+        }.fit(width_px, height_px)
+    }
+}
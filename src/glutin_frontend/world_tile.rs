@@ -1,6 +1,7 @@
 use glutin_frontend::tile_map::{TileMapData, UpdateTileMapData, ShaderTemplateInfo};
 use tile_buffer::TileBufferCell;
 use tile::{NUM_TILE_CHANNELS, OVERLAY_CHANNEL};
+use content::LightColour;
 
 const TILE_STATUS_IDX: usize = 3;
 const STATUS_BITS_PER_CHANNEL: usize = 2;
@@ -9,6 +10,17 @@ const TILE_STATUS_VISIBLE: u32 = 1 << (CHANNEL_STATUS_BITS + 0);
 const CHANNEL_PRESENT_OFFSET: usize = 0;
 const CHANNEL_DIMINISH_OFFSET: usize = 1;
 
+// The light colour is packed as rgb565 into the upper half of the
+// word holding the last (odd-numbered) channel.
+const TILE_LIGHT_IDX: usize = NUM_TILE_CHANNELS / 2;
+const TILE_LIGHT_SHIFT: u32 = 16;
+const TILE_LIGHT_MASK: u32 = 0xffff0000;
+
+// How stale a remembered cell is, packed into the status word
+// above the status bits.
+const TILE_STALENESS_SHIFT: u32 = 16;
+const TILE_STALENESS_MAX: u32 = 0xff;
+
 pub fn shader_template_info() -> ShaderTemplateInfo<'static> {
     btreemap!{
         "NUM_TILE_CHANNELS" => NUM_TILE_CHANNELS as u32,
@@ -17,6 +29,10 @@ pub fn shader_template_info() -> ShaderTemplateInfo<'static> {
         "STATUS_BITS_PER_CHANNEL" => STATUS_BITS_PER_CHANNEL as u32,
         "CHANNEL_PRESENT_OFFSET" => CHANNEL_PRESENT_OFFSET as u32,
         "CHANNEL_DIMINISH_OFFSET" => CHANNEL_DIMINISH_OFFSET as u32,
+        "TILE_LIGHT_IDX" => TILE_LIGHT_IDX as u32,
+        "TILE_LIGHT_SHIFT" => TILE_LIGHT_SHIFT,
+        "TILE_STALENESS_SHIFT" => TILE_STALENESS_SHIFT,
+        "TILE_STALENESS_MAX" => TILE_STALENESS_MAX,
     }
 }
 
@@ -76,6 +92,27 @@ fn set_channel(cell: &mut TileMapData, channel_idx: usize, x: u8, y: u8) {
     cell.data[idx] = f32::from_bits(result);
 }
 
+fn set_light(cell: &mut TileMapData, light: LightColour) {
+    let light = light.saturate();
+    let r = (light.r * 31.0) as u32;
+    let g = (light.g * 63.0) as u32;
+    let b = (light.b * 31.0) as u32;
+    let packed = (r << 11) | (g << 5) | b;
+
+    let current = cell.data[TILE_LIGHT_IDX].to_bits();
+    let result = (current & !TILE_LIGHT_MASK) | (packed << TILE_LIGHT_SHIFT);
+    cell.data[TILE_LIGHT_IDX] = f32::from_bits(result);
+}
+
+fn set_staleness(cell: &mut TileMapData, staleness: f64) {
+    let packed = (staleness.max(0.0).min(1.0) * TILE_STALENESS_MAX as f64) as u32;
+
+    let current = cell.data[TILE_STATUS_IDX].to_bits();
+    let mask = TILE_STALENESS_MAX << TILE_STALENESS_SHIFT;
+    let result = (current & !mask) | (packed << TILE_STALENESS_SHIFT);
+    cell.data[TILE_STATUS_IDX] = f32::from_bits(result);
+}
+
 pub fn init_tile_map_data(data: &mut [TileMapData]) {
     for cell in data.iter_mut() {
         for i in 0..NUM_TILE_CHANNELS {
@@ -102,5 +139,7 @@ impl<'a> UpdateTileMapData for &'a TileBufferCell {
                 clear_visible(cell);
             }
         }
+        set_light(cell, self.light);
+        set_staleness(cell, self.staleness);
     }
 }
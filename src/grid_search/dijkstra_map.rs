@@ -0,0 +1,179 @@
+use std::collections::BinaryHeap;
+use std::cmp::{self, Ordering};
+use grid::StaticGrid;
+use direction::Direction;
+use coord::LookupCoord;
+use cgmath::Vector2;
+
+#[derive(Debug)]
+struct DijkstraCell {
+    seen_seq: u64,
+    cost: u32,
+}
+
+impl Default for DijkstraCell {
+    fn default() -> Self {
+        DijkstraCell {
+            seen_seq: 0,
+            cost: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DijkstraNode {
+    cost: u32,
+    coord: Vector2<i32>,
+}
+
+impl PartialEq for DijkstraNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for DijkstraNode {}
+
+impl PartialOrd for DijkstraNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DijkstraNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+// Stores the cost of reaching each cell from the nearest of a set of
+// sources. Cells that weren't reached in the most recent computation
+// are distinguished by their sequence number, so the grid never needs
+// to be cleared.
+#[derive(Debug)]
+pub struct DijkstraMap {
+    grid: StaticGrid<DijkstraCell>,
+    heap: BinaryHeap<DijkstraNode>,
+    seq: u64,
+}
+
+impl DijkstraMap {
+    pub fn new(width: usize, height: usize) -> Self {
+        DijkstraMap {
+            grid: StaticGrid::new_default(width, height),
+            heap: BinaryHeap::new(),
+            seq: 0,
+        }
+    }
+
+    pub fn width(&self) -> usize { self.grid.width() }
+    pub fn height(&self) -> usize { self.grid.height() }
+
+    // Returns the cost of reaching the given cell from the nearest
+    // source, or None if it was unreachable.
+    pub fn get(&self, coord: Vector2<i32>) -> Option<u32> {
+        self.grid.get(coord).and_then(|cell| {
+            if cell.seen_seq == self.seq {
+                Some(cell.cost)
+            } else {
+                None
+            }
+        })
+    }
+
+    // Recomputes the map from the given sources. The cost function returns
+    // the cost of entering a cell, or None if the cell can't be entered.
+    // Costs less than 1 are treated as 1. Cells costing more than max to
+    // reach are left unreached.
+    pub fn compute<Sources, Dirs, Grid, Cell, CostFn>(&mut self,
+                                                      grid: &Grid,
+                                                      sources: Sources,
+                                                      directions: Dirs,
+                                                      cost: CostFn,
+                                                      max: u32)
+        where Sources: IntoIterator<Item=Vector2<i32>>,
+              Dirs: Copy + IntoIterator<Item=Direction>,
+              Grid: LookupCoord<Item=Cell>,
+              CostFn: Fn(&Cell) -> Option<u32>,
+    {
+        self.heap.clear();
+        self.seq += 1;
+
+        for source in sources {
+            if let Some(cell) = self.grid.get_mut(source) {
+                cell.seen_seq = self.seq;
+                cell.cost = 0;
+                self.heap.push(DijkstraNode {
+                    cost: 0,
+                    coord: source,
+                });
+            }
+        }
+
+        while let Some(DijkstraNode { cost: current_cost, coord: current_coord }) = self.heap.pop() {
+
+            // skip nodes which were reached more cheaply after being queued
+            if let Some(cell) = self.grid.get(current_coord) {
+                if cell.cost != current_cost {
+                    continue;
+                }
+            }
+
+            for direction in directions {
+                let coord = current_coord + direction.vector();
+
+                let step_cost = if let Some(grid_cell) = grid.lookup_coord(coord) {
+                    if let Some(step_cost) = cost(grid_cell) {
+                        cmp::max(step_cost, 1)
+                    } else {
+                        continue;
+                    }
+                } else {
+                    continue;
+                };
+
+                let next_cost = current_cost + step_cost;
+                if next_cost > max {
+                    continue;
+                }
+
+                if let Some(cell) = self.grid.get_mut(coord) {
+                    if cell.seen_seq == self.seq && cell.cost <= next_cost {
+                        continue;
+                    }
+                    cell.seen_seq = self.seq;
+                    cell.cost = next_cost;
+                } else {
+                    continue;
+                }
+
+                self.heap.push(DijkstraNode {
+                    cost: next_cost,
+                    coord: coord,
+                });
+            }
+        }
+    }
+
+    // Returns the direction from the given cell to the neighbour closest
+    // to a source, if there is a neighbour closer than the given cell.
+    // The given cell needn't have been reached itself.
+    pub fn descend<Dirs>(&self, coord: Vector2<i32>, directions: Dirs) -> Option<Direction>
+        where Dirs: IntoIterator<Item=Direction>,
+    {
+        let mut best_cost = self.get(coord).unwrap_or(::std::u32::MAX);
+
+        let mut best_direction = None;
+
+        for direction in directions {
+            if let Some(cost) = self.get(coord + direction.vector()) {
+                if cost < best_cost {
+                    best_cost = cost;
+                    best_direction = Some(direction);
+                }
+            }
+        }
+
+        best_direction
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::VecDeque;
+use std::collections::{VecDeque, BinaryHeap};
+use std::cmp::Ordering;
 use std::result;
 use std::slice;
 use grid:: StaticGrid;
@@ -7,6 +8,9 @@ use cgmath::Vector2;
 use best::BestMapNonEmpty;
 use coord::LookupCoord;
 
+mod dijkstra_map;
+pub use self::dijkstra_map::DijkstraMap;
+
 #[derive(Debug)]
 pub enum Error {
     InvalidGridSize,
@@ -76,6 +80,12 @@ impl Path {
 struct Node {
     seen_seq: u64,
     entry_direction: Option<Direction>,
+
+    // strength remaining when the node was reached during a spread
+    strength: u32,
+
+    // cost of the cheapest known route to the node during a weighted search
+    cost: u32,
 }
 
 impl Default for Node {
@@ -83,13 +93,72 @@ impl Default for Node {
         Node {
             seen_seq: 0,
             entry_direction: None,
+            strength: 0,
+            cost: 0,
         }
     }
 }
 
+#[derive(Debug)]
+struct SpreadNode {
+    strength: u32,
+    coord: Vector2<i32>,
+}
+
+impl PartialEq for SpreadNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.strength == other.strength
+    }
+}
+
+impl Eq for SpreadNode {}
+
+impl PartialOrd for SpreadNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SpreadNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strength.cmp(&other.strength)
+    }
+}
+
+// Entry in the priority queue of a weighted search. The
+// cheapest node is the greatest, so it is popped first.
+#[derive(Debug)]
+struct CostNode {
+    priority: u32,
+    cost: u32,
+    coord: Vector2<i32>,
+}
+
+impl PartialEq for CostNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for CostNode {}
+
+impl PartialOrd for CostNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CostNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
 #[derive(Debug)]
 pub struct SearchEnv {
     queue: VecDeque<Vector2<i32>>,
+    heap: BinaryHeap<SpreadNode>,
+    cost_heap: BinaryHeap<CostNode>,
     node_grid: StaticGrid<Node>,
     seq: u64,
 }
@@ -98,6 +167,8 @@ impl SearchEnv {
     pub fn new(width: usize, height: usize) -> Self {
         SearchEnv {
             queue: VecDeque::new(),
+            heap: BinaryHeap::new(),
+            cost_heap: BinaryHeap::new(),
             node_grid: StaticGrid::new_default(width, height),
             seq: 0,
         }
@@ -105,6 +176,8 @@ impl SearchEnv {
 
     fn clear(&mut self) {
         self.queue.clear();
+        self.heap.clear();
+        self.cost_heap.clear();
         self.seq += 1;
     }
 
@@ -316,3 +389,168 @@ pub fn bfs_predicate<Dirs, Grid, Cell, PredFn, CanEnterFn>(
 
     Err(Error::NoPath)
 }
+
+// Spreads a value outwards from the start coordinate, reducing it by the
+// cost of entering each cell, until it runs out. The visit function is
+// called once for each cell reached, with the strongest value that reached it.
+pub fn spread<Dirs, Grid, Cell, CostFn, VisitFn>(
+        env: &mut SearchEnv,
+        grid: &Grid,
+        start: Vector2<i32>,
+        directions: Dirs,
+        strength: u32,
+        cost: CostFn,
+        mut visit: VisitFn) -> Result<()>
+    where Dirs: Copy + IntoIterator<Item=Direction>,
+          Grid: LookupCoord<Item=Cell>,
+          CostFn: Fn(&Cell) -> u32,
+          VisitFn: FnMut(Vector2<i32>, u32),
+{
+    env.clear();
+
+    env.see_first(start)?;
+    if let Some(node) = env.node_grid.get_mut(start) {
+        node.strength = strength;
+    }
+    env.heap.push(SpreadNode {
+        strength: strength,
+        coord: start,
+    });
+
+    while let Some(SpreadNode { strength, coord }) = env.heap.pop() {
+
+        // skip nodes which were reached more strongly after being queued
+        if let Some(node) = env.node_grid.get(coord) {
+            if node.strength != strength {
+                continue;
+            }
+        }
+
+        visit(coord, strength);
+
+        for direction in directions {
+            let next_coord = coord + direction.vector();
+
+            let next_strength = if let Some(cell) = grid.lookup_coord(next_coord) {
+                let cost = cost(cell);
+                if cost >= strength {
+                    continue;
+                }
+                strength - cost
+            } else {
+                continue;
+            };
+
+            if let Some(node) = env.node_grid.get_mut(next_coord) {
+                if node.seen_seq == env.seq && node.strength >= next_strength {
+                    continue;
+                }
+                node.seen_seq = env.seq;
+                node.strength = next_strength;
+                node.entry_direction = Some(direction);
+            } else {
+                continue;
+            }
+
+            env.heap.push(SpreadNode {
+                strength: next_strength,
+                coord: next_coord,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Lower bound on the cost of travelling between two cells, given that
+// entering a cell costs at least 1. This is admissible for searches
+// over either cardinal or all eight directions.
+fn heuristic(a: Vector2<i32>, b: Vector2<i32>) -> u32 {
+    let delta = b - a;
+    ::std::cmp::max(delta.x.abs(), delta.y.abs()) as u32
+}
+
+// Finds the cheapest path from start to dest. The cost function returns
+// the cost of entering a cell, or None if the cell can't be entered.
+// Costs less than 1 are treated as 1.
+pub fn astar<Dirs, Grid, Cell, CostFn>(
+        env: &mut SearchEnv,
+        knowledge: &Grid,
+        start: Vector2<i32>,
+        directions: Dirs,
+        dest: Vector2<i32>,
+        cost: CostFn,
+        path: &mut Path) -> Result<()>
+    where Dirs: Copy + IntoIterator<Item=Direction>,
+          Grid: LookupCoord<Item=Cell>,
+          CostFn: Fn(&Cell) -> Option<u32>,
+{
+    env.clear();
+
+    env.see_first(start)?;
+    if let Some(node) = env.node_grid.get_mut(start) {
+        node.cost = 0;
+    }
+    env.cost_heap.push(CostNode {
+        priority: heuristic(start, dest),
+        cost: 0,
+        coord: start,
+    });
+
+    if let Some(knowledge_cell) = knowledge.lookup_coord(dest) {
+        if cost(knowledge_cell).is_none() {
+            return Err(Error::CantEnterDestination);
+        }
+    } else {
+        return Err(Error::DestinationOutsideKnowledge);
+    }
+
+    while let Some(CostNode { cost: current_cost, coord: current_coord, .. }) = env.cost_heap.pop() {
+
+        if current_coord == dest {
+            return env.construct_path(dest, path);
+        }
+
+        // skip nodes which were reached more cheaply after being queued
+        if let Some(node) = env.node_grid.get(current_coord) {
+            if node.cost != current_cost {
+                continue;
+            }
+        }
+
+        for direction in directions {
+            let coord = current_coord + direction.vector();
+
+            let step_cost = if let Some(knowledge_cell) = knowledge.lookup_coord(coord) {
+                if let Some(step_cost) = cost(knowledge_cell) {
+                    ::std::cmp::max(step_cost, 1)
+                } else {
+                    continue;
+                }
+            } else {
+                continue;
+            };
+
+            let next_cost = current_cost + step_cost;
+
+            if let Some(node) = env.node_grid.get_mut(coord) {
+                if node.seen_seq == env.seq && node.cost <= next_cost {
+                    continue;
+                }
+                node.seen_seq = env.seq;
+                node.cost = next_cost;
+                node.entry_direction = Some(direction);
+            } else {
+                continue;
+            }
+
+            env.cost_heap.push(CostNode {
+                priority: next_cost + heuristic(coord, dest),
+                cost: next_cost,
+                coord: coord,
+            });
+        }
+    }
+
+    Err(Error::NoPath)
+}
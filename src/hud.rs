@@ -0,0 +1,68 @@
+use entity_store::*;
+use content::{Weapon, ItemType, StatusEffect};
+
+// An item listed on the inventory screen, chosen by pressing its letter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InventoryEntry {
+    pub letter: char,
+    pub item_type: ItemType,
+}
+
+impl InventoryEntry {
+    pub fn text(&self) -> String {
+        format!("{}) {}", self.letter, self.item_type.to_str())
+    }
+}
+
+// What the inventory screen is being shown for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryPrompt {
+    View,
+    Use,
+    Drop,
+}
+
+impl InventoryPrompt {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            InventoryPrompt::View => "Inventory",
+            InventoryPrompt::Use => "Use which item?",
+            InventoryPrompt::Drop => "Drop which item?",
+        }
+    }
+}
+
+// The state of the player shown alongside the map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hud {
+    pub weapon: Option<Weapon>,
+    pub status_effects: Vec<StatusEffect>,
+}
+
+impl Hud {
+    pub fn new(entity_id: EntityId, entity_store: &EntityStore) -> Self {
+        Hud {
+            weapon: entity_store.weapons.get(&entity_id).and_then(|arsenal| arsenal.equipped()).cloned(),
+            status_effects: entity_store.status_effects.get(&entity_id)
+                .map(|effects| effects.iter().cloned().collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    // The hud as lines of text, from top to bottom.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        if let Some(weapon) = self.weapon {
+            lines.push(format!("{}: {}/{} loaded, {} spare", weapon.weapon_type.to_str(),
+                               weapon.loaded, weapon.weapon_type.capacity(), weapon.reserve));
+        }
+
+        if !self.status_effects.is_empty() {
+            let names = self.status_effects.iter().map(|effect| effect.to_str()).collect::<Vec<_>>();
+            lines.push(names.join(", "));
+        }
+
+        lines
+    }
+}
@@ -11,6 +11,7 @@ pub enum InputEvent {
     Escape,
     Return,
     Space,
+    Resize(u32, u32),
 }
 
 pub struct ExternalEvent {
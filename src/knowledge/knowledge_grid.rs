@@ -1,11 +1,23 @@
 use entity_store::EntityStore;
 use spatial_hash::SpatialHashCell;
 use observation::ObservationMetadata;
+use content::LightColour;
 use cgmath::Vector2;
 
 pub trait KnowledgeGrid {
     fn update_cell(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
                    entity_store: &EntityStore) -> ObservationMetadata;
 
+    // Called instead of update_cell when the view of a cell may be partially
+    // obscured. Visibility is the fraction of the view reaching the cell.
+    fn update_cell_obscured(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                            entity_store: &EntityStore, _visibility: f64) -> ObservationMetadata {
+        self.update_cell(coord, spatial_hash_cell, entity_store)
+    }
+
     fn set_time(&mut self, time: u64);
+
+    fn update_light(&mut self, _coord: Vector2<i32>, _light: LightColour) -> ObservationMetadata {
+        Default::default()
+    }
 }
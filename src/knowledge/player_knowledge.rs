@@ -1,7 +1,8 @@
+use std::collections::{hash_map, HashMap};
 use entity_store::{EntityId, EntityStore};
 use spatial_hash::SpatialHashCell;
 use grid::StaticGrid;
-use content::{ComplexTile, OverlayType, TileType};
+use content::{ComplexTile, OverlayType, TileType, WaterDepth, KeyId, LightColour, OBSCURED_VISIBILITY, GHOST_MAX_AGE};
 use knowledge::KnowledgeGrid;
 use observation::ObservationMetadata;
 use coord::LookupCoord;
@@ -23,19 +24,41 @@ pub struct PlayerKnowledgeCell {
     pub wall: bool,
     pub low_tile: bool,
     pub tile_front: Option<TileType>,
+    pub water_depth: Option<WaterDepth>,
     pub solid: bool,
     pub door: Option<EntityId>,
+    pub lock: Option<KeyId>,
     pub enemy: Option<EntityId>,
     pub player: bool,
     pub veil_cell: VeilCell,
+    pub veil_forecast: Option<u32>,
+    pub light: LightColour,
+    pub visibility: f64,
 }
 
+// The last place an enemy was seen
+#[derive(Debug, Clone, Copy)]
+pub struct EnemyGhost {
+    pub coord: Vector2<i32>,
+    pub last_seen: u64,
+}
+
+impl EnemyGhost {
+    pub fn age(&self, time: u64) -> u64 {
+        time.saturating_sub(self.last_seen)
+    }
+}
+
+pub type EnemyGhostIter<'a> = hash_map::Iter<'a, EntityId, EnemyGhost>;
+
 #[derive(Debug)]
 pub struct PlayerKnowledgeGrid {
     last_updated: u64,
     current_time: u64,
     player_coord: Option<Vector2<i32>>,
     last_player_coord: Option<Vector2<i32>>,
+    heard_coord: Option<Vector2<i32>>,
+    ghosts: HashMap<EntityId, EnemyGhost>,
     grid: StaticGrid<PlayerKnowledgeCell>,
 }
 
@@ -48,27 +71,39 @@ impl Default for PlayerKnowledgeCell {
             wall: false,
             low_tile: false,
             tile_front: None,
+            water_depth: None,
             solid: false,
             door: None,
+            lock: None,
             enemy: None,
             player: false,
             veil_cell: Default::default(),
+            veil_forecast: None,
+            light: LightColour::black(),
+            visibility: 1.0,
         }
     }
 }
 
 impl PlayerKnowledgeCell {
-    fn update(&mut self, spatial_hash_cell: &SpatialHashCell, entity_store: &EntityStore, time: u64) -> ObservationMetadata {
+    fn update(&mut self, spatial_hash_cell: &SpatialHashCell, entity_store: &EntityStore,
+              time: u64, visibility: f64) -> ObservationMetadata {
 
         let mut changed = false;
 
-        if self.last_updated < spatial_hash_cell.last_updated {
+        // creatures can't be made out in obscured cells
+        let obscured = visibility < OBSCURED_VISIBILITY;
+
+        if self.last_updated < spatial_hash_cell.last_updated || obscured != self.is_obscured() {
             self.tiles.clear();
             self.wall = false;
             for entity_id in spatial_hash_cell.tile_set.iter() {
                 if entity_store.invisible.contains(entity_id) {
                     continue;
                 }
+                if obscured && entity_store.forgetable.contains(entity_id) {
+                    continue;
+                }
                 if let Some(tile) = entity_store.tile.get(entity_id) {
                     if let Some(priority) = entity_store.tile_priority.get(entity_id) {
                         self.tiles.push(PlayerKnowledgeTile {
@@ -84,13 +119,23 @@ impl PlayerKnowledgeCell {
             }
             self.solid = spatial_hash_cell.solid_count > 0;
             self.door = spatial_hash_cell.door_set.iter().next().cloned();
-            self.enemy = spatial_hash_cell.enemy_set.iter().next().cloned();
-            self.player = spatial_hash_cell.player_count > 0;
+            self.lock = self.door.and_then(|id| entity_store.lock.get(&id)).cloned();
+            if obscured {
+                self.enemy = None;
+                self.player = false;
+            } else {
+                self.enemy = spatial_hash_cell.enemy_set.iter().next().cloned();
+                self.player = spatial_hash_cell.player_count > 0;
+            }
             self.veil_cell.current = spatial_hash_cell.veil_current_count > 0;
             self.veil_cell.next = spatial_hash_cell.veil_next_count > 0;
+            self.veil_forecast = spatial_hash_cell.veil_slot_set.iter()
+                .filter_map(|id| entity_store.veil_forecast.get(id))
+                .min().cloned();
             self.low_tile = spatial_hash_cell.low_tile_count > 0;
             self.tile_front = spatial_hash_cell.tile_front_set.iter().next()
                 .and_then(|id| entity_store.tile_front.get(id)).cloned();
+            self.water_depth = WaterDepth::of_cell(spatial_hash_cell);
 
             changed = true;
         }
@@ -101,10 +146,15 @@ impl PlayerKnowledgeCell {
         };
 
         self.last_updated = time;
+        self.visibility = visibility;
 
         md
     }
 
+    pub fn is_obscured(&self) -> bool {
+        self.visibility < OBSCURED_VISIBILITY
+    }
+
     pub fn is_visible(&self, time: u64) -> bool {
         self.last_updated == time
     }
@@ -117,6 +167,8 @@ impl PlayerKnowledgeGrid {
             current_time: 0,
             player_coord: None,
             last_player_coord: None,
+            heard_coord: None,
+            ghosts: HashMap::new(),
             grid: StaticGrid::new_default(width, height),
         }
     }
@@ -140,11 +192,53 @@ impl PlayerKnowledgeGrid {
     pub fn clear_last_player_coord(&mut self) {
         self.last_player_coord = None;
     }
+
+    // Learns from an ally where the player was seen,
+    // unless the player is already in view.
+    pub fn alert(&mut self, coord: Vector2<i32>) {
+        if self.player_coord.is_none() {
+            self.last_player_coord = Some(coord);
+        }
+    }
+
+    pub fn heard_coord(&self) -> Option<Vector2<i32>> {
+        self.heard_coord
+    }
+
+    pub fn hear(&mut self, coord: Vector2<i32>) {
+        self.heard_coord = Some(coord);
+    }
+
+    pub fn clear_heard_coord(&mut self) {
+        self.heard_coord = None;
+    }
+
+    pub fn ghosts(&self) -> EnemyGhostIter {
+        self.ghosts.iter()
+    }
+
+    // Ghosts are forgotten once they get too old, or once their
+    // cell is seen again without the enemy in it.
+    fn forget_ghosts(&mut self) {
+        let time = self.current_time;
+        let grid = &self.grid;
+        self.ghosts.retain(|_, ghost| {
+            let seen_since = grid.get(ghost.coord)
+                .map(|cell| cell.last_updated > ghost.last_seen)
+                .unwrap_or(true);
+            !seen_since && ghost.age(time) < GHOST_MAX_AGE
+        });
+    }
 }
 
 impl KnowledgeGrid for PlayerKnowledgeGrid {
     fn update_cell(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
                    entity_store: &EntityStore) -> ObservationMetadata {
+        self.update_cell_obscured(coord, spatial_hash_cell, entity_store, 1.0)
+    }
+
+    fn update_cell_obscured(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                            entity_store: &EntityStore, visibility: f64) -> ObservationMetadata {
 
         if let Some(knowledge_cell) = self.grid.get_mut(coord) {
             if knowledge_cell.last_updated == self.current_time {
@@ -155,17 +249,26 @@ impl KnowledgeGrid for PlayerKnowledgeGrid {
                 self.last_updated = self.current_time;
             }
 
-            if spatial_hash_cell.player_count > 0 {
+            if spatial_hash_cell.player_count > 0 && visibility >= OBSCURED_VISIBILITY {
                 self.player_coord = Some(coord);
                 self.last_player_coord = Some(coord);
             } else if let Some(player_coord) = self.player_coord {
                 if player_coord == coord {
-                    // there's no player here, but we previously saw the player here
+                    // the player can't be seen here, but we previously saw the player here
                     self.player_coord = None;
                 }
             }
 
-            knowledge_cell.update(spatial_hash_cell, entity_store, self.current_time)
+            if visibility >= OBSCURED_VISIBILITY {
+                for id in spatial_hash_cell.enemy_set.iter() {
+                    self.ghosts.insert(*id, EnemyGhost {
+                        coord: coord,
+                        last_seen: self.current_time,
+                    });
+                }
+            }
+
+            knowledge_cell.update(spatial_hash_cell, entity_store, self.current_time, visibility)
         } else {
             Default::default()
         }
@@ -173,6 +276,21 @@ impl KnowledgeGrid for PlayerKnowledgeGrid {
 
     fn set_time(&mut self, time: u64) {
         self.current_time = time;
+        self.forget_ghosts();
+    }
+
+    fn update_light(&mut self, coord: Vector2<i32>, light: LightColour) -> ObservationMetadata {
+        if let Some(knowledge_cell) = self.grid.get_mut(coord) {
+            if knowledge_cell.light != light {
+                knowledge_cell.light = light;
+                return ObservationMetadata {
+                    changed: true,
+                    new: false,
+                };
+            }
+        }
+
+        Default::default()
     }
 }
 
@@ -1,16 +1,18 @@
 use std::result;
 
-use entity_store::EntityId;
+use entity_store::*;
 use game_env::GameEnv;
-use level_env::LevelEnv;
+use level_env::{LevelEnv, TurnEvent};
 use terrain;
-use content::VeilStepInfo;
+use veil_config::VeilConfig;
 use knowledge::PlayerKnowledgeGrid;
-use behaviour::BehaviourState;
+use behaviour::{BehaviourState, BehaviourConfig};
+use veil_hazard::VeilHazardConfig;
 use turn::{self, TurnEnv, TurnResolution};
 use renderer::GameRenderer;
 use input::GameInput;
 use meta_action::DebugAction;
+use content::{ActionType, WaterDepth, Message};
 
 #[derive(Debug)]
 pub enum Error {
@@ -18,8 +20,11 @@ pub enum Error {
 }
 pub type Result<T> = result::Result<T, Error>;
 
+const LEVEL_NAME: &'static str = "string_demo";
+
 enum GameLoopExit {
     Quit,
+    PlayerDied(Message),
 }
 
 pub fn launch<Ren: GameRenderer, Inp: GameInput>(renderer: &mut Ren, input: &mut Inp) {
@@ -29,37 +34,65 @@ pub fn launch<Ren: GameRenderer, Inp: GameInput>(renderer: &mut Ren, input: &mut
     let width = 80;
     let height = 30;
 
-    let veil_step_info = VeilStepInfo {
-        x: 0.02,
-        y: 0.01,
-        z: 0.02,
-        min: -0.02,
-        max: 0.02,
-    };
-
-    let mut level = LevelEnv::new(width, height, &mut game.rng, &veil_step_info);
+    let mut level = LevelEnv::new(width, height, &mut game.rng, VeilConfig::load(LEVEL_NAME),
+                                  BehaviourConfig::load(), VeilHazardConfig::load());
 
     let md = terrain::string_demo::generate(&mut game.change, &mut game.id_allocator, &mut game.rng);
     let player_id = md.player_id.expect("missing player");
 
     level.commit(&mut game.change, game.time);
 
-    init_player(player_id, &mut level, veil_step_info);
+    init_player(player_id, &mut level);
     game_loop(player_id, &mut game, &mut level, renderer, input).expect("Error in game loop");
 }
 
-fn init_player(player_id: EntityId, level: &mut LevelEnv, veil_step_info: VeilStepInfo) {
-    for (id, period) in level.entity_store.turn_period.iter() {
-        level.turn_schedule.insert(*id, *period);
-        if *id != player_id {
-            level.behaviour.insert(*id, BehaviourState::new());
-            level.knowledge.insert(*id, PlayerKnowledgeGrid::new(level.spatial_hash.width(), level.spatial_hash.height()));
+fn init_player(player_id: EntityId, level: &mut LevelEnv) {
+    let ids: Vec<EntityId> = level.entity_store.turn_period.keys().cloned().collect();
+    for id in ids {
+        if id == player_id {
+            level.turn_schedule.insert(TurnEvent::Turn(id), level.entity_store.turn_period[&id]);
+        } else {
+            add_npc(id, level);
         }
     }
 
+    let veil_step_info = level.veil_config.step;
     level.entity_store.veil_step_info.insert(player_id, veil_step_info);
 }
 
+fn add_npc(id: EntityId, level: &mut LevelEnv) {
+    if let Some(period) = level.entity_store.turn_period.get(&id) {
+        level.turn_schedule.insert(TurnEvent::Turn(id), *period);
+    }
+    let position = *level.entity_store.position.get(&id).expect("Missing position");
+    level.behaviour.insert(id, BehaviourState::new(position));
+    level.knowledge.insert(id, PlayerKnowledgeGrid::new(level.spatial_hash.width(), level.spatial_hash.height()));
+}
+
+// Forgets the npcs which have been removed from the level, such as
+// those killed by the veil or by projectiles.
+fn forget_removed_npcs(level: &mut LevelEnv) {
+    let removed = level.behaviour.keys()
+        .filter(|id| !level.entity_store.npc.contains(id))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for id in removed {
+        level.behaviour.remove(&id);
+        level.knowledge.remove(&id);
+        level.fov_cache.remove(id);
+    }
+}
+
+// Shows a message which ends the game, leaving it up until a key is pressed.
+fn show_final_message<Ren: GameRenderer, Inp: GameInput>(message: Message, renderer: &mut Ren, input: &mut Inp) {
+    renderer.show_message(message);
+    renderer.clear();
+    renderer.draw();
+    renderer.publish();
+    input.next_input();
+}
+
 fn take_turn<Ren: GameRenderer, Inp: GameInput>(player_id: EntityId, entity_id: EntityId,
                                                 game: &mut GameEnv, level: &mut LevelEnv,
                                                 renderer: &mut Ren, input: &mut Inp)
@@ -75,23 +108,30 @@ fn take_turn<Ren: GameRenderer, Inp: GameInput>(player_id: EntityId, entity_id:
         id_allocator: &mut game.id_allocator,
         spatial_hash: &mut level.spatial_hash,
         behaviour_env: &mut level.behaviour_env,
+        behaviour_config: &level.behaviour_config,
         player_id: player_id,
         entity_id: entity_id,
         player_knowledge: &mut level.player_knowledge,
         knowledge: &mut level.knowledge,
         behaviour: &mut level.behaviour,
-        shadowcast: &mut game.shadowcast,
+        fov: &mut game.fov,
+        fov_cache: &mut level.fov_cache,
+        light_grid: &mut level.light_grid,
+        weather: &level.weather,
         time: &mut game.time,
         policy: &mut game.policy,
+        projectile_config: &game.projectile_config,
         rng: &mut game.rng,
         schedule: &mut game.action_schedule,
     }.take_turn()
 }
 
-fn pre_turn(entity_id: EntityId, game: &mut GameEnv, level: &mut LevelEnv) {
+fn pre_turn(entity_id: EntityId,
+            game: &mut GameEnv, level: &mut LevelEnv) -> Option<GameLoopExit> {
 
-    if let Some(veil_step_info) = level.entity_store.veil_step_info.get(&entity_id) {
+    if let Some(veil_step_info) = level.entity_store.veil_step_info.get_mut(&entity_id) {
         level.veil_state.step(&mut game.rng, veil_step_info);
+        level.veil_config.escalate(level.veil_state.num_steps(), veil_step_info);
     }
 
     if level.entity_store.player.contains(&entity_id) {
@@ -100,25 +140,105 @@ fn pre_turn(entity_id: EntityId, game: &mut GameEnv, level: &mut LevelEnv) {
                                 &level.spatial_hash,
                                 &level.veil_state);
 
+        level.weather.step(&mut game.rng);
+        game.policy.weather_update(&level.weather,
+                                   &mut game.change,
+                                   &level.entity_store,
+                                   &level.spatial_hash,
+                                   &mut game.id_allocator,
+                                   &mut game.rng);
+    }
+
+    game.policy.status_update(entity_id, &mut game.change, &level.entity_store, &level.spatial_hash);
+
+    let spawned = game.policy.veil_hazards(entity_id,
+                                           &level.veil_hazards,
+                                           &mut game.change,
+                                           &level.entity_store,
+                                           &level.spatial_hash,
+                                           &level.veil_state,
+                                           &mut game.id_allocator,
+                                           &mut game.rng);
+
+    level.behaviour_env.flow_field.invalidate_on_change(&game.change, &level.entity_store);
+
+    game.time += 1;
+    level.spatial_hash.update(&level.entity_store, &game.change, game.time);
+    level.entity_store.commit_change(&mut game.change);
+
+    if let Some(id) = spawned {
+        add_npc(id, level);
+    }
+
+    if let Some(message) = game.policy.player_death() {
+        return Some(GameLoopExit::PlayerDied(message));
+    }
+
+    None
+}
+
+// turns taken in water or while slowed take longer
+fn turn_period(entity_id: EntityId, level: &LevelEnv) -> Option<u64> {
+    level.entity_store.turn_period.get(&entity_id).map(|period| {
+        let water_cost = level.entity_store.position.get(&entity_id)
+            .and_then(|position| level.spatial_hash.get(*position))
+            .and_then(WaterDepth::of_cell)
+            .map(WaterDepth::turn_cost)
+            .unwrap_or(0);
+        let status_cost = level.entity_store.status_effects.get(&entity_id)
+            .map(|effects| effects.turn_penalty())
+            .unwrap_or(0);
+        period + water_cost + status_cost
+    })
+}
+
+// Gives entities the status effects queued by the policy, scheduling
+// each to wear off once its duration has passed.
+fn apply_statuses(game: &mut GameEnv, level: &mut LevelEnv) {
+    let mut applied = false;
+
+    for (id, effect, duration) in game.policy.drain_statuses() {
+        if !level.entity_store.position.contains_key(&id) {
+            continue;
+        }
+        applied = true;
+        let expiry = level.turn_schedule.absolute_time() + duration;
+        let mut effects = post_change_get!(level.entity_store, game.change, id, status_effects)
+            .cloned().unwrap_or_default();
+        effects.insert(effect, expiry);
+        game.change.status_effects.insert(id, effects);
+        level.turn_schedule.insert(TurnEvent::Action(ActionType::ExpireStatus(id, effect, expiry)), duration);
+    }
+
+    if applied {
         game.time += 1;
-        level.spatial_hash.update(&level.entity_store, &game.change, game.time);
-        level.entity_store.commit_change(&mut game.change);
+        level.commit(&mut game.change, game.time);
     }
 }
 
 fn handle_turn_resolution<Ren: GameRenderer>(resolution: TurnResolution,
                                              player_id: EntityId, entity_id: EntityId,
-                                             level: &mut LevelEnv,
+                                             game: &mut GameEnv, level: &mut LevelEnv,
                                              renderer: &mut Ren) -> Option<GameLoopExit>  {
     match resolution {
         TurnResolution::Reschedule => {
-            if let Some(period) = level.entity_store.turn_period.get(&entity_id) {
-                level.turn_schedule.insert(entity_id, *period);
+            if let Some(period) = turn_period(entity_id, level) {
+                // time lost to slow actions such as reloading
+                let delay = level.entity_store.turn_delay.get(&entity_id).cloned().unwrap_or(0);
+                if delay > 0 {
+                    game.change.turn_delay.remove(entity_id);
+                    game.time += 1;
+                    level.commit(&mut game.change, game.time);
+                }
+                level.turn_schedule.insert(TurnEvent::Turn(entity_id), period + delay);
             }
         }
         TurnResolution::External(_) => {
             return Some(GameLoopExit::Quit);
         }
+        // Turns of removed entities are left on the schedule rather than
+        // cancelled, and are dropped here when they come up. Their status
+        // expiries carry the expiry time, so stale ones change nothing.
         TurnResolution::NoEntity => (),
         TurnResolution::Debug(debug_action) => {
             if let Some(info) = level.entity_store.veil_step_info.get_mut(&player_id) {
@@ -151,9 +271,21 @@ fn handle_turn_resolution<Ren: GameRenderer>(resolution: TurnResolution,
                     config.diminishing_lighting = !config.diminishing_lighting;
                     renderer.set_config(config);
                 }
+                DebugAction::ToggleVeilForecast => {
+                    let mut config = renderer.config();
+                    config.veil_forecast = !config.veil_forecast;
+                    renderer.set_config(config);
+                }
+                DebugAction::PrintAlertStates => {
+                    for (id, state) in level.behaviour.iter() {
+                        if let Some(position) = level.entity_store.position.get(id) {
+                            println!("{} at ({}, {}): {:?}", id, position.x, position.y, state.alert);
+                        }
+                    }
+                }
                 _ => (),
             }
-            level.turn_schedule.insert(entity_id, 0);
+            level.turn_schedule.insert(TurnEvent::Turn(entity_id), 0);
         }
     }
 
@@ -164,16 +296,36 @@ fn game_loop<Ren: GameRenderer, Inp: GameInput>(player_id: EntityId,
                                                 game: &mut GameEnv, level: &mut LevelEnv,
                                                 renderer: &mut Ren, input: &mut Inp) -> Result<GameLoopExit> {
     while let Some(entry) = level.turn_schedule.next() {
-        let entity_id = entry.value;
+        let entity_id = match entry.value {
+            TurnEvent::Turn(entity_id) => entity_id,
+            TurnEvent::Action(action) => {
+                action.populate(&mut game.change, &level.entity_store, &mut game.id_allocator);
+                game.time += 1;
+                level.commit(&mut game.change, game.time);
+                continue;
+            }
+        };
 
-        pre_turn(entity_id, game, level);
+        if let Some(exit) = pre_turn(entity_id, game, level) {
+            if let GameLoopExit::PlayerDied(message) = exit {
+                show_final_message(message, renderer, input);
+            }
+            return Ok(exit);
+        }
+        forget_removed_npcs(level);
+        apply_statuses(game, level);
 
         let resolution = take_turn(player_id, entity_id,
                                    game, level,
                                    renderer, input).map_err(Error::TurnError)?;
 
+        for message in game.policy.drain_messages() {
+            renderer.show_message(message);
+        }
+        forget_removed_npcs(level);
+        apply_statuses(game, level);
 
-        if let Some(exit) = handle_turn_resolution(resolution, player_id, entity_id, level, renderer) {
+        if let Some(exit) = handle_turn_resolution(resolution, player_id, entity_id, game, level, renderer) {
             return Ok(exit);
         }
 
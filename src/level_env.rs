@@ -4,24 +4,46 @@ use entity_store::{EntityId, EntityStore, EntityStoreChange};
 use spatial_hash::SpatialHashTable;
 use schedule::Schedule;
 use knowledge::PlayerKnowledgeGrid;
-use behaviour::{BehaviourState, BehaviourEnv};
+use behaviour::{BehaviourState, BehaviourEnv, BehaviourConfig};
 use veil_state::VeilState;
-use content::VeilStepInfo;
+use veil_hazard::VeilHazardConfig;
+use light_grid::LightGrid;
+use observation::FovCacheTable;
+use veil_config::VeilConfig;
+use weather::Weather;
+use content::{ActionType, INITIAL_RAIN_INTENSITY};
+
+// Things which happen on the turn schedule
+#[derive(Debug, Clone, Copy)]
+pub enum TurnEvent {
+    // an entity takes its turn
+    Turn(EntityId),
+    // something happens between turns, such as a status effect wearing off
+    Action(ActionType),
+}
 
 pub struct LevelEnv {
     pub entity_store: EntityStore,
     pub spatial_hash: SpatialHashTable,
-    pub turn_schedule: Schedule<EntityId>,
+    pub turn_schedule: Schedule<TurnEvent>,
     pub knowledge: HashMap<EntityId, PlayerKnowledgeGrid>,
     pub behaviour: HashMap<EntityId, BehaviourState>,
     pub behaviour_env: BehaviourEnv,
+    pub behaviour_config: BehaviourConfig,
+    pub veil_config: VeilConfig,
     pub veil_state: VeilState,
+    pub veil_hazards: VeilHazardConfig,
     pub player_knowledge: PlayerKnowledgeGrid,
+    pub light_grid: LightGrid,
+    pub weather: Weather,
+    pub fov_cache: FovCacheTable,
 }
 
 impl LevelEnv {
     pub fn new<R: Rng>(width: usize, height: usize,
-                       rng: &mut R, veil_step_info: &VeilStepInfo) -> Self {
+                       rng: &mut R, veil_config: VeilConfig,
+                       behaviour_config: BehaviourConfig,
+                       veil_hazards: VeilHazardConfig) -> Self {
         LevelEnv {
             entity_store: EntityStore::new(),
             spatial_hash: SpatialHashTable::new(width, height),
@@ -29,8 +51,14 @@ impl LevelEnv {
             knowledge: HashMap::new(),
             behaviour: HashMap::new(),
             behaviour_env: BehaviourEnv::new(width, height),
-            veil_state: VeilState::new(width, height, rng, veil_step_info),
+            behaviour_config: behaviour_config,
+            veil_state: VeilState::from_config(width, height, rng, &veil_config),
+            veil_config: veil_config,
+            veil_hazards: veil_hazards,
             player_knowledge: PlayerKnowledgeGrid::new(width, height),
+            light_grid: LightGrid::new(width, height),
+            weather: Weather::new(INITIAL_RAIN_INTENSITY, 0.0),
+            fov_cache: FovCacheTable::new(),
         }
     }
 
@@ -0,0 +1,184 @@
+use cgmath::Vector2;
+use entity_store::EntityStore;
+use spatial_hash::{SpatialHashTable, SpatialHashCell};
+use grid::StaticGrid;
+use knowledge::KnowledgeGrid;
+use observation::ObservationMetadata;
+use observation::shadowcast::{self, ShadowcastEnv};
+use content::{Light, LightColour, AMBIENT_OUTSIDE, AMBIENT_INSIDE, VEIL_GLOW,
+              LIGHT_DIM_THRESHOLD, LIGHT_BRIGHT_THRESHOLD, DARK_VISION_DISTANCE};
+
+#[derive(Debug)]
+struct LightCell {
+    colour: LightColour,
+    seen_seq: u64,
+}
+
+impl Default for LightCell {
+    fn default() -> Self {
+        LightCell {
+            colour: LightColour::black(),
+            seen_seq: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LightGrid {
+    grid: StaticGrid<LightCell>,
+
+    // time at which the grid was last computed
+    last_updated: Option<u64>,
+
+    // incremented for each light source, so a cell visited
+    // twice by the same shadowcast is only lit once
+    seq: u64,
+
+    // the light currently being cast
+    source: Vector2<i32>,
+    light: Light,
+}
+
+fn ambient(cell: &SpatialHashCell) -> LightColour {
+    let mut colour = if cell.inside_count == 0 {
+        AMBIENT_OUTSIDE
+    } else {
+        AMBIENT_INSIDE
+    };
+
+    if cell.veil_current_count > 0 {
+        colour += VEIL_GLOW;
+    }
+
+    colour
+}
+
+impl LightGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        LightGrid {
+            grid: StaticGrid::new_default(width, height),
+            last_updated: None,
+            seq: 0,
+            source: Vector2::new(0, 0),
+            light: Light::new(0, LightColour::black()),
+        }
+    }
+
+    pub fn get(&self, coord: Vector2<i32>) -> Option<LightColour> {
+        self.grid.get(coord).map(|c| c.colour)
+    }
+
+    pub fn intensity(&self, coord: Vector2<i32>) -> f64 {
+        self.get(coord).map(|c| c.intensity()).unwrap_or(0.0)
+    }
+
+    // Recomputes the light level of every cell, unless it has
+    // already been computed for the current time.
+    pub fn update(&mut self, shadowcast: &mut ShadowcastEnv, spatial_hash: &SpatialHashTable,
+                  entity_store: &EntityStore, time: u64) {
+
+        if self.last_updated == Some(time) {
+            return;
+        }
+        self.last_updated = Some(time);
+
+        for (light_cell, sh_cell) in izip!(self.grid.iter_mut(), spatial_hash.iter()) {
+            light_cell.colour = ambient(sh_cell);
+        }
+
+        for (id, light) in entity_store.light.iter() {
+            if let Some(position) = entity_store.position.get(id) {
+                self.seq += 1;
+                self.source = *position;
+                self.light = *light;
+                shadowcast::observe(shadowcast, *position, spatial_hash, light.radius, entity_store, time, self);
+            }
+        }
+    }
+}
+
+impl KnowledgeGrid for LightGrid {
+    fn update_cell(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                   entity_store: &EntityStore) -> ObservationMetadata {
+        self.update_cell_obscured(coord, spatial_hash_cell, entity_store, 1.0)
+    }
+
+    // light passing through partially opaque cells is dimmed
+    fn update_cell_obscured(&mut self, coord: Vector2<i32>, _spatial_hash_cell: &SpatialHashCell,
+                            _entity_store: &EntityStore, visibility: f64) -> ObservationMetadata {
+
+        if let Some(cell) = self.grid.get_mut(coord) {
+            if cell.seen_seq == self.seq {
+                return Default::default();
+            }
+            cell.seen_seq = self.seq;
+
+            let delta = coord - self.source;
+            let distance = ((delta.x * delta.x + delta.y * delta.y) as f64).sqrt();
+            let falloff = 1.0 - distance / (self.light.radius as f64 + 1.0);
+            if falloff > 0.0 {
+                cell.colour += self.light.colour * falloff * visibility;
+            }
+        }
+
+        Default::default()
+    }
+
+    fn set_time(&mut self, _time: u64) {}
+}
+
+// Wraps a knowledge grid such that only cells which are lit well
+// enough to be seen from the eye are reported to it.
+pub struct LitKnowledgeGrid<'a, K: 'a + KnowledgeGrid> {
+    pub knowledge: &'a mut K,
+    pub light_grid: &'a LightGrid,
+    pub eye: Vector2<i32>,
+    pub vision_distance: u32,
+}
+
+impl<'a, K: KnowledgeGrid> LitKnowledgeGrid<'a, K> {
+    fn can_see(&self, coord: Vector2<i32>, light: LightColour) -> bool {
+        let delta = coord - self.eye;
+        let distance_squared = (delta.x * delta.x + delta.y * delta.y) as u32;
+
+        if distance_squared < DARK_VISION_DISTANCE * DARK_VISION_DISTANCE {
+            return true;
+        }
+
+        let intensity = light.intensity();
+
+        if intensity >= LIGHT_BRIGHT_THRESHOLD {
+            // the shadowcast range limits how far away bright cells can be seen
+            true
+        } else if intensity >= LIGHT_DIM_THRESHOLD {
+            distance_squared < self.vision_distance * self.vision_distance
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a, K: KnowledgeGrid> KnowledgeGrid for LitKnowledgeGrid<'a, K> {
+    fn update_cell(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                   entity_store: &EntityStore) -> ObservationMetadata {
+
+        self.update_cell_obscured(coord, spatial_hash_cell, entity_store, 1.0)
+    }
+
+    fn update_cell_obscured(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                            entity_store: &EntityStore, visibility: f64) -> ObservationMetadata {
+
+        let light = self.light_grid.get(coord).unwrap_or_default();
+
+        if self.can_see(coord, light) {
+            self.knowledge.update_cell_obscured(coord, spatial_hash_cell, entity_store, visibility) |
+                self.knowledge.update_light(coord, light)
+        } else {
+            Default::default()
+        }
+    }
+
+    fn set_time(&mut self, time: u64) {
+        self.knowledge.set_time(time);
+    }
+}
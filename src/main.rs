@@ -44,6 +44,7 @@ mod invert_ord;
 mod behaviour;
 mod coord;
 mod render_overlay;
+mod hud;
 mod limits;
 mod schedule;
 mod frame;
@@ -59,6 +60,13 @@ mod player_render;
 mod commit;
 mod perlin;
 mod veil_state;
+mod veil_config;
+mod veil_hazard;
+mod projectile_config;
+mod light_grid;
+mod noise;
+mod weather;
+mod alert;
 mod terrain;
 mod tile;
 mod tile_desc;
@@ -11,6 +11,8 @@ pub enum DebugAction {
     ChangeVeilStep(Vector3<f64>),
     TogglePlayerOmniscient,
     ToggleDiminishingLighting,
+    ToggleVeilForecast,
+    PrintAlertStates,
     Wait,
 }
 
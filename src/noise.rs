@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use entity_store::*;
+use spatial_hash::{SpatialHashTable, SpatialHashCell};
+use knowledge::PlayerKnowledgeGrid;
+use grid_search::{self, SearchEnv};
+use direction::DirectionsCardinal;
+use content::{NOISE_ATTENUATION, CLOSED_DOOR_NOISE_ATTENUATION, WALL_NOISE_ATTENUATION};
+
+pub fn attenuation(cell: &SpatialHashCell) -> u32 {
+    if cell.solid_count == 0 {
+        NOISE_ATTENUATION
+    } else if cell.door_set.is_empty() {
+        WALL_NOISE_ATTENUATION
+    } else {
+        CLOSED_DOOR_NOISE_ATTENUATION
+    }
+}
+
+// Spreads each noise made in the given change through the map, and
+// tells each npc within earshot where the noise came from. Each noise
+// is made quieter by the given muffling (e.g. from the weather).
+pub fn propagate(change: &EntityStoreChange,
+                 entity_store: &EntityStore,
+                 spatial_hash: &SpatialHashTable,
+                 search_env: &mut SearchEnv,
+                 knowledge: &mut HashMap<EntityId, PlayerKnowledgeGrid>,
+                 muffling: u32) -> grid_search::Result<()> {
+
+    for (id, noise_change) in change.noise.iter() {
+        if let &DataChangeType::Insert(loudness) = noise_change {
+            let loudness = loudness.saturating_sub(muffling);
+            if loudness == 0 {
+                continue;
+            }
+            if let Some(source) = post_change_get!(entity_store, change, *id, position).cloned() {
+                grid_search::spread(search_env, spatial_hash, source, DirectionsCardinal, loudness, attenuation,
+                                    |coord, _| {
+                    if let Some(cell) = spatial_hash.get(coord) {
+                        for npc_id in cell.npc_set.iter() {
+                            if let Some(npc_knowledge) = knowledge.get_mut(npc_id) {
+                                npc_knowledge.hear(source);
+                            }
+                        }
+                    }
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,26 +1,45 @@
+use rand::Rng;
 use knowledge::PlayerKnowledgeGrid;
 use behaviour::*;
 use entity_store::*;
 use spatial_hash::*;
-use content::ActionType;
-use observation::shadowcast::ShadowcastEnv;
+use content::{ActionType, BehaviourType};
+use observation::FovEnv;
+use observation::FovCacheTable;
+use observation::ObservationMetadata;
+use light_grid::LightGrid;
 use entity_observe;
 
-pub struct NpcActEnv<'a> {
+pub struct NpcActEnv<'a, R: 'a + Rng> {
     pub entity_store: &'a EntityStore,
     pub spatial_hash: &'a SpatialHashTable,
     pub entity_id: EntityId,
     pub knowledge: &'a mut PlayerKnowledgeGrid,
     pub behaviour_env: &'a mut BehaviourEnv,
     pub behaviour_state: &'a mut BehaviourState,
-    pub shadowcast: &'a mut ShadowcastEnv,
+    pub behaviour_config: &'a BehaviourConfig,
+    pub fov: &'a mut FovEnv,
+    pub fov_cache: &'a mut FovCacheTable,
+    pub light_grid: &'a mut LightGrid,
     pub time: &'a mut u64,
+    pub rng: &'a mut R,
 }
 
 pub type Error = entity_observe::Error;
 pub type Result<T> = entity_observe::Result<T>;
 
-impl<'a> NpcActEnv<'a> {
+impl<'a, R: Rng> NpcActEnv<'a, R> {
+    fn run_node(&mut self, node: BehaviourNode, metadata: ObservationMetadata) -> Option<ActionType> {
+        match node {
+            BehaviourNode::Attack => attack::attack(self.entity_id, self.entity_store, self.spatial_hash, self.knowledge, self.behaviour_env, self.behaviour_state),
+            BehaviourNode::Search => search::search(self.entity_id, self.entity_store, self.knowledge, *self.time, self.behaviour_env, self.behaviour_state),
+            BehaviourNode::Patrol => patrol::patrol(self.entity_id, self.entity_store, self.knowledge, metadata, *self.time, self.behaviour_env, self.behaviour_state),
+            BehaviourNode::Flee => flee::flee(self.entity_id, self.entity_store, self.knowledge, *self.time),
+            BehaviourNode::Guard => guard::guard(self.entity_id, self.entity_store, self.knowledge, self.behaviour_env, self.behaviour_state),
+            BehaviourNode::Wander => wander::wander(self.entity_id, self.entity_store, self.knowledge, *self.time, self.rng),
+        }
+    }
+
     pub fn act(&mut self) -> Result<ActionType> {
 
         let metadata = entity_observe::entity_observe(self.entity_id,
@@ -28,10 +47,21 @@ impl<'a> NpcActEnv<'a> {
                                                       self.spatial_hash,
                                                       *self.time,
                                                       self.knowledge,
-                                                      self.shadowcast)?;
+                                                      self.fov,
+                                                      self.fov_cache,
+                                                      self.light_grid)?;
+
+        let behaviour_type = self.entity_store.behaviour_type.get(&self.entity_id)
+            .cloned().unwrap_or(BehaviourType::Undead);
+
+        // the first node to choose an action decides what to do
+        let behaviour_config = self.behaviour_config;
+        for node in behaviour_config.nodes(behaviour_type) {
+            if let Some(action) = self.run_node(*node, metadata) {
+                return Ok(action);
+            }
+        }
 
-        Ok(attack::attack(self.entity_id, self.entity_store, self.knowledge, self.behaviour_env, self.behaviour_state).or_else(|| {
-            patrol::patrol(self.entity_id, self.entity_store, self.knowledge, metadata, *self.time, self.behaviour_env, self.behaviour_state)
-        }).unwrap_or(ActionType::Null))
+        Ok(ActionType::Null)
     }
 }
@@ -0,0 +1,65 @@
+use cgmath::Vector2;
+use spatial_hash::SpatialHashTable;
+use entity_store::EntityStore;
+use knowledge::KnowledgeGrid;
+use observation::{shadowcast, omniscient, ObservationMetadata};
+use observation::shadowcast::ShadowcastEnv;
+use observation::quadrant_shadowcast::QuadrantShadowcastEnv;
+use content::FovType;
+
+pub trait FieldOfView {
+    fn observe<K: KnowledgeGrid>(&mut self, eye: Vector2<i32>, world: &SpatialHashTable, distance: u32,
+                                 entity_store: &EntityStore, time: u64, knowledge: &mut K) -> ObservationMetadata;
+}
+
+impl FieldOfView for ShadowcastEnv {
+    fn observe<K: KnowledgeGrid>(&mut self, eye: Vector2<i32>, world: &SpatialHashTable, distance: u32,
+                                 entity_store: &EntityStore, time: u64, knowledge: &mut K) -> ObservationMetadata {
+        shadowcast::observe(self, eye, world, distance, entity_store, time, knowledge)
+    }
+}
+
+impl FieldOfView for QuadrantShadowcastEnv {
+    fn observe<K: KnowledgeGrid>(&mut self, eye: Vector2<i32>, world: &SpatialHashTable, distance: u32,
+                                 entity_store: &EntityStore, time: u64, knowledge: &mut K) -> ObservationMetadata {
+        QuadrantShadowcastEnv::observe(self, eye, world, distance, entity_store, time, knowledge)
+    }
+}
+
+pub struct Omniscient;
+
+impl FieldOfView for Omniscient {
+    fn observe<K: KnowledgeGrid>(&mut self, _eye: Vector2<i32>, world: &SpatialHashTable, _distance: u32,
+                                 entity_store: &EntityStore, time: u64, knowledge: &mut K) -> ObservationMetadata {
+        omniscient::observe(world, entity_store, time, knowledge)
+    }
+}
+
+// Holds the state of each field of view algorithm,
+// so they can be chosen between per observer.
+pub struct FovEnv {
+    pub shadowcast: ShadowcastEnv,
+    pub symmetric: QuadrantShadowcastEnv,
+    pub permissive: QuadrantShadowcastEnv,
+}
+
+impl FovEnv {
+    pub fn new() -> Self {
+        FovEnv {
+            shadowcast: ShadowcastEnv::new(),
+            symmetric: QuadrantShadowcastEnv::symmetric(),
+            permissive: QuadrantShadowcastEnv::permissive(),
+        }
+    }
+
+    pub fn observe<K: KnowledgeGrid>(&mut self, fov_type: FovType,
+                                     eye: Vector2<i32>, world: &SpatialHashTable, distance: u32,
+                                     entity_store: &EntityStore, time: u64, knowledge: &mut K) -> ObservationMetadata {
+        match fov_type {
+            FovType::Shadowcast => self.shadowcast.observe(eye, world, distance, entity_store, time, knowledge),
+            FovType::SymmetricShadowcast => self.symmetric.observe(eye, world, distance, entity_store, time, knowledge),
+            FovType::Permissive => self.permissive.observe(eye, world, distance, entity_store, time, knowledge),
+            FovType::Omniscient => Omniscient.observe(eye, world, distance, entity_store, time, knowledge),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use cgmath::Vector2;
+use entity_store::{EntityId, EntityStore};
+use spatial_hash::{SpatialHashTable, SpatialHashCell};
+use knowledge::KnowledgeGrid;
+use observation::ObservationMetadata;
+use observation::FovEnv;
+use content::FovType;
+
+#[derive(Debug, Clone, Copy)]
+struct CachedCell {
+    coord: Vector2<i32>,
+    opacity: f64,
+    visibility: f64,
+}
+
+// The cells visible to an observer, along with the opacity of each
+// cell at the time it was seen.
+#[derive(Debug)]
+struct FovCache {
+    fov_type: FovType,
+    eye: Vector2<i32>,
+    distance: u32,
+    time: u64,
+    cells: Vec<CachedCell>,
+}
+
+impl FovCache {
+    fn is_valid(&self, fov_type: FovType, eye: Vector2<i32>, distance: u32, spatial_hash: &SpatialHashTable) -> bool {
+        if self.fov_type != fov_type || self.eye != eye || self.distance != distance {
+            return false;
+        }
+
+        // Only cells which were visible can change what is visible,
+        // and only cells updated since the cache was populated can
+        // have changed their opacity.
+        for cell in self.cells.iter() {
+            if let Some(sh_cell) = spatial_hash.get(cell.coord) {
+                if sh_cell.last_updated > self.time && sh_cell.opacity_total != cell.opacity {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+// Passes cells on to a knowledge grid, recording each one.
+struct FovRecorder<'a, K: 'a + KnowledgeGrid> {
+    knowledge: &'a mut K,
+    cells: &'a mut Vec<CachedCell>,
+}
+
+impl<'a, K: KnowledgeGrid> KnowledgeGrid for FovRecorder<'a, K> {
+    fn update_cell(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                   entity_store: &EntityStore) -> ObservationMetadata {
+
+        self.update_cell_obscured(coord, spatial_hash_cell, entity_store, 1.0)
+    }
+
+    fn update_cell_obscured(&mut self, coord: Vector2<i32>, spatial_hash_cell: &SpatialHashCell,
+                            entity_store: &EntityStore, visibility: f64) -> ObservationMetadata {
+
+        self.cells.push(CachedCell {
+            coord: coord,
+            opacity: spatial_hash_cell.opacity_total,
+            visibility: visibility,
+        });
+
+        self.knowledge.update_cell_obscured(coord, spatial_hash_cell, entity_store, visibility)
+    }
+
+    fn set_time(&mut self, time: u64) {
+        self.knowledge.set_time(time);
+    }
+}
+
+// Remembers the field of view of each observer, so it only needs
+// to be recomputed when something that could change it changes.
+#[derive(Debug)]
+pub struct FovCacheTable {
+    caches: HashMap<EntityId, FovCache>,
+}
+
+impl FovCacheTable {
+    pub fn new() -> Self {
+        FovCacheTable {
+            caches: HashMap::new(),
+        }
+    }
+
+    pub fn remove(&mut self, id: EntityId) {
+        self.caches.remove(&id);
+    }
+
+    pub fn observe<K: KnowledgeGrid>(&mut self, id: EntityId, env: &mut FovEnv, fov_type: FovType,
+                                     eye: Vector2<i32>,
+                                     world: &SpatialHashTable, distance: u32,
+                                     entity_store: &EntityStore, time: u64,
+                                     knowledge: &mut K) -> ObservationMetadata {
+
+        if let Some(cache) = self.caches.get(&id) {
+            if cache.is_valid(fov_type, eye, distance, world) {
+                knowledge.set_time(time);
+
+                let mut metadata = ObservationMetadata::default();
+                for cell in cache.cells.iter() {
+                    if let Some(sh_cell) = world.get(cell.coord) {
+                        metadata |= knowledge.update_cell_obscured(cell.coord, sh_cell, entity_store, cell.visibility);
+                    }
+                }
+
+                return metadata;
+            }
+        }
+
+        let cache = self.caches.entry(id).or_insert_with(|| FovCache {
+            fov_type: fov_type,
+            eye: eye,
+            distance: distance,
+            time: time,
+            cells: Vec::new(),
+        });
+
+        cache.fov_type = fov_type;
+        cache.eye = eye;
+        cache.distance = distance;
+        cache.time = time;
+        cache.cells.clear();
+
+        let metadata = {
+            let mut recorder = FovRecorder {
+                knowledge: knowledge,
+                cells: &mut cache.cells,
+            };
+
+            env.observe(fov_type, eye, world, distance, entity_store, time, &mut recorder)
+        };
+
+        // field of view algorithms may visit a cell more than once
+        cache.cells.sort_by_key(|cell| (cell.coord.y, cell.coord.x));
+        cache.cells.dedup_by_key(|cell| cell.coord);
+
+        metadata
+    }
+}
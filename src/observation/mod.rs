@@ -1,4 +1,9 @@
 pub mod shadowcast;
 pub mod omniscient;
+pub mod quadrant_shadowcast;
+pub mod fov;
+pub use self::fov::FovEnv;
+mod fov_cache;
+pub use self::fov_cache::FovCacheTable;
 mod metadata;
 pub use self::metadata::*;
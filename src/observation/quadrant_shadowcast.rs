@@ -0,0 +1,234 @@
+use cgmath::Vector2;
+use spatial_hash::{SpatialHashTable, SpatialHashCell};
+use entity_store::EntityStore;
+use knowledge::KnowledgeGrid;
+use observation::ObservationMetadata;
+
+const NUM_QUADRANTS: usize = 4;
+
+// Which cells in the visible area are reported to the knowledge grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reveal {
+    // Floor cells are only visible if their centre is in view. This makes
+    // vision symmetric: if a can see b, then b can see a.
+    Symmetric,
+
+    // Cells are visible if any part of them is in view.
+    Permissive,
+}
+
+// Slope of a line from the eye, as a fraction of columns per row.
+// The denominator is always positive.
+#[derive(Debug, Clone, Copy)]
+struct Slope {
+    num: i32,
+    den: i32,
+}
+
+impl Slope {
+    fn new(num: i32, den: i32) -> Self {
+        Slope {
+            num: num,
+            den: den,
+        }
+    }
+
+    // Slope of a line to the left edge of a cell, along the
+    // centre line of its row. Walls block the view from these
+    // points, so they effectively have diamond-shaped corners.
+    fn cell_edge(col: i32, depth: i32) -> Self {
+        Self::new(2 * col - 1, 2 * depth)
+    }
+}
+
+fn floor_div(a: i32, b: i32) -> i32 {
+    let d = a / b;
+    if a % b != 0 && a < 0 {
+        d - 1
+    } else {
+        d
+    }
+}
+
+fn ceil_div(a: i32, b: i32) -> i32 {
+    -floor_div(-a, b)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    depth: i32,
+    start: Slope,
+    end: Slope,
+    // fraction of the view reaching this row, reduced by
+    // partially opaque cells in the rows before it
+    visibility: f64,
+}
+
+impl Row {
+    // first column whose centre isn't left of the start slope, rounding ties up
+    fn min_col(&self) -> i32 {
+        floor_div(2 * self.depth * self.start.num + self.start.den, 2 * self.start.den)
+    }
+
+    // last column whose centre isn't right of the end slope, rounding ties down
+    fn max_col(&self) -> i32 {
+        ceil_div(2 * self.depth * self.end.num - self.end.den, 2 * self.end.den)
+    }
+
+    fn next(&self, start: Slope, end: Slope, visibility: f64) -> Self {
+        Row {
+            depth: self.depth + 1,
+            start: start,
+            end: end,
+            visibility: visibility,
+        }
+    }
+}
+
+// whether the centre of the cell lies between the slopes
+fn is_symmetric(depth: i32, col: i32, start: Slope, end: Slope) -> bool {
+    col * start.den >= depth * start.num && col * end.den <= depth * end.num
+}
+
+// whether any part of the cell lies strictly between the slopes
+fn is_partly_visible(depth: i32, col: i32, start: Slope, end: Slope) -> bool {
+    // the cell's edges, doubled to keep them whole
+    let (left, right) = (2 * col - 1, 2 * col + 1);
+    let (near, far) = (2 * depth - 1, 2 * depth + 1);
+
+    // the slopes to the corners furthest clockwise and anticlockwise
+    let min = if left < 0 { Slope::new(left, near) } else { Slope::new(left, far) };
+    let max = if right > 0 { Slope::new(right, near) } else { Slope::new(right, far) };
+
+    max.num * start.den > start.num * max.den && min.num * end.den < end.num * min.den
+}
+
+fn transform(quadrant: usize, eye: Vector2<i32>, depth: i32, col: i32) -> Vector2<i32> {
+    match quadrant {
+        0 => Vector2::new(eye.x + col, eye.y - depth),
+        1 => Vector2::new(eye.x + depth, eye.y + col),
+        2 => Vector2::new(eye.x + col, eye.y + depth),
+        _ => Vector2::new(eye.x - depth, eye.y + col),
+    }
+}
+
+// fraction of the view which passes through a cell, where cells
+// outside the world block vision entirely
+fn visibility_through(cell: Option<&SpatialHashCell>, visibility: f64) -> f64 {
+    cell.map(|c| (visibility - c.opacity_total).max(0.0)).unwrap_or(0.0)
+}
+
+// Shadowcast which scans each quadrant row by row,
+// using exact fractions for slopes.
+pub struct QuadrantShadowcastEnv {
+    reveal: Reveal,
+    stack: Vec<Row>,
+}
+
+impl QuadrantShadowcastEnv {
+    pub fn symmetric() -> Self {
+        QuadrantShadowcastEnv {
+            reveal: Reveal::Symmetric,
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn permissive() -> Self {
+        QuadrantShadowcastEnv {
+            reveal: Reveal::Permissive,
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn observe<K: KnowledgeGrid>(&mut self, eye: Vector2<i32>, world: &SpatialHashTable, distance: u32,
+                                     entity_store: &EntityStore, time: u64, knowledge: &mut K) -> ObservationMetadata {
+
+        knowledge.set_time(time);
+
+        let mut metadata = if let Some(eye_cell) = world.get(eye) {
+            knowledge.update_cell(eye, eye_cell, entity_store)
+        } else {
+            Default::default()
+        };
+
+        let distance_squared = (distance * distance) as i32;
+
+        for quadrant in 0..NUM_QUADRANTS {
+            self.stack.push(Row {
+                depth: 1,
+                start: Slope::new(-1, 1),
+                end: Slope::new(1, 1),
+                visibility: 1.0,
+            });
+
+            while let Some(row) = self.stack.pop() {
+                if row.depth > distance as i32 {
+                    continue;
+                }
+
+                let mut start = row.start;
+                let mut prev_visibility = None;
+
+                let min_col = row.min_col();
+                let max_col = row.max_col();
+
+                // cells either side of those scanned can be partly in view
+                let (first_col, last_col) = match self.reveal {
+                    Reveal::Symmetric => (min_col, max_col),
+                    Reveal::Permissive => (min_col - 1, max_col + 1),
+                };
+
+                for col in first_col..(last_col + 1) {
+                    let coord = transform(quadrant, eye, row.depth, col);
+                    let cell = world.get(coord);
+                    let visibility = visibility_through(cell, row.visibility);
+                    let wall = visibility == 0.0;
+                    let scanned = col >= min_col && col <= max_col;
+
+                    if let Some(cell) = cell {
+                        let between = coord - eye;
+                        let in_range = between.x * between.x + between.y * between.y < distance_squared;
+                        let revealed = (scanned && wall) || match self.reveal {
+                            Reveal::Symmetric => is_symmetric(row.depth, col, start, row.end),
+                            Reveal::Permissive => is_partly_visible(row.depth, col, start, row.end),
+                        };
+
+                        if in_range && revealed {
+                            if row.visibility < 1.0 {
+                                metadata |= knowledge.update_cell_obscured(coord, cell, entity_store, row.visibility);
+                            } else {
+                                metadata |= knowledge.update_cell(coord, cell, entity_store);
+                            }
+                        }
+                    }
+
+                    // only the scanned cells cast shadows on the next row
+                    if !scanned {
+                        continue;
+                    }
+
+                    // each change in visibility ends a region of the next row
+                    if let Some(prev_visibility) = prev_visibility {
+                        if visibility != prev_visibility {
+                            let edge = Slope::cell_edge(col, row.depth);
+                            if prev_visibility > 0.0 {
+                                self.stack.push(row.next(start, edge, prev_visibility));
+                            }
+                            start = edge;
+                        }
+                    }
+
+                    prev_visibility = Some(visibility);
+                }
+
+                if let Some(prev_visibility) = prev_visibility {
+                    if prev_visibility > 0.0 {
+                        self.stack.push(row.next(start, row.end, prev_visibility));
+                    }
+                }
+            }
+        }
+
+        metadata
+    }
+}
@@ -332,7 +332,11 @@ fn scan<K: KnowledgeGrid>(stack: &mut Vec<Frame>, args: &OctantArgs, scan: &Scan
         let between = coord - args.eye;
         let distance_squared = between.x * between.x + between.y * between.y;
         if distance_squared < args.distance_squared {
-            metadata |= knowledge.update_cell(coord, cell, entity_store);
+            if scan.frame.visibility < 1.0 {
+                metadata |= knowledge.update_cell_obscured(coord, cell, entity_store, scan.frame.visibility);
+            } else {
+                metadata |= knowledge.update_cell(coord, cell, entity_store);
+            }
         }
 
         // compute current visibility
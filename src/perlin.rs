@@ -255,6 +255,160 @@ impl PerlinGrid {
     }
 }
 
+fn default_octaves() -> usize { 1 }
+fn default_lacunarity() -> f64 { 2.0 }
+fn default_persistence() -> f64 { 0.5 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FractalConfig {
+    // number of layers of noise summed together
+    #[serde(default = "default_octaves")]
+    pub octaves: usize,
+    // frequency multiplier between successive octaves
+    #[serde(default = "default_lacunarity")]
+    pub lacunarity: f64,
+    // amplitude multiplier between successive octaves
+    #[serde(default = "default_persistence")]
+    pub persistence: f64,
+    // maximum distance (in grid cells) by which sample points are displaced
+    #[serde(default)]
+    pub warp_strength: f64,
+}
+
+impl Default for FractalConfig {
+    fn default() -> Self {
+        FractalConfig {
+            octaves: default_octaves(),
+            lacunarity: default_lacunarity(),
+            persistence: default_persistence(),
+            warp_strength: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Octave {
+    perlin: PerlinGrid,
+    frequency: f64,
+    amplitude: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct DomainWarp {
+    x: PerlinGrid,
+    y: PerlinGrid,
+    strength: f64,
+}
+
+// Sums several octaves of perlin noise, optionally sampling at points
+// displaced by another noise field. Coordinates, scrolling and mutation
+// are all in units of the lowest frequency octave, so with one octave and
+// no warping this behaves exactly like a single PerlinGrid.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FractalNoise {
+    octaves: Vec<Octave>,
+    warp: Option<DomainWarp>,
+    total_amplitude: f64,
+    max_x: f64,
+    max_y: f64,
+}
+
+impl FractalNoise {
+    pub fn new<R: Rng>(width: usize, height: usize, wrap_type: PerlinWrapType,
+                       config: &FractalConfig, r: &mut R) -> Self {
+
+        assert!(config.octaves > 0, "Fractal noise needs at least one octave");
+
+        let mut octaves = Vec::with_capacity(config.octaves);
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+        let mut total_amplitude = 0.0;
+
+        for _ in 0..config.octaves {
+            // covers the same area as the lowest octave's grid at this frequency
+            let octave_width = (width as f64 * frequency).ceil() as usize;
+            let octave_height = (height as f64 * frequency).ceil() as usize;
+            octaves.push(Octave {
+                perlin: PerlinGrid::new(octave_width, octave_height, wrap_type, r),
+                frequency: frequency,
+                amplitude: amplitude,
+            });
+            total_amplitude += amplitude;
+            frequency *= config.lacunarity;
+            amplitude *= config.persistence;
+        }
+
+        let warp = if config.warp_strength > 0.0 {
+            Some(DomainWarp {
+                x: PerlinGrid::new(width, height, wrap_type, r),
+                y: PerlinGrid::new(width, height, wrap_type, r),
+                strength: config.warp_strength,
+            })
+        } else {
+            None
+        };
+
+        FractalNoise {
+            octaves: octaves,
+            warp: warp,
+            total_amplitude: total_amplitude,
+            max_x: width as f64,
+            max_y: height as f64,
+        }
+    }
+
+    pub fn mutate<R: Rng>(&mut self, r: &mut R, value: f64) {
+        for octave in self.octaves.iter_mut() {
+            octave.perlin.mutate(r, value);
+        }
+        if let Some(warp) = self.warp.as_mut() {
+            warp.x.mutate(r, value);
+            warp.y.mutate(r, value);
+        }
+    }
+
+    pub fn scroll<R: Rng>(&mut self, r: &mut R, x: f64, y: f64) {
+        for octave in self.octaves.iter_mut() {
+            octave.perlin.scroll(r, x * octave.frequency, y * octave.frequency);
+        }
+        if let Some(warp) = self.warp.as_mut() {
+            warp.x.scroll(r, x, y);
+            warp.y.scroll(r, x, y);
+        }
+    }
+
+    fn warp(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        if let Some(warp) = self.warp.as_ref() {
+            warp.x.noise(x, y).and_then(|dx| warp.y.noise(x, y).map(|dy| {
+                // the grids can't be sampled at negative coordinates
+                ((x + dx * warp.strength).max(0.0).min(self.max_x),
+                 (y + dy * warp.strength).max(0.0).min(self.max_y))
+            }))
+        } else {
+            Some((x, y))
+        }
+    }
+
+    pub fn noise(&self, x: f64, y: f64) -> Option<f64> {
+        let (x, y) = if let Some(coord) = self.warp(x, y) {
+            coord
+        } else {
+            return None;
+        };
+
+        let mut total = 0.0;
+        for octave in self.octaves.iter() {
+            if let Some(noise) = octave.perlin.noise(x * octave.frequency, y * octave.frequency) {
+                total += noise * octave.amplitude;
+            } else {
+                return None;
+            }
+        }
+
+        Some(total / self.total_amplitude)
+    }
+}
+
 const NUM_GRADIENTS: usize = 16;
 const GRADIENT_MASK: usize = 0xf;
 static GRADIENTS: [Vector3<f64>; NUM_GRADIENTS] = [
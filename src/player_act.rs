@@ -1,8 +1,8 @@
 use std::result;
 use rand::Rng;
-use content::ActionType;
+use content::{ActionType, DoorState, Weapon};
 use meta_action::*;
-use direction::Direction;
+use direction::{Direction, DirectionsCardinal};
 use cgmath::{Vector2, Vector3};
 use straight_line::*;
 use render_overlay::RenderOverlay;
@@ -10,14 +10,19 @@ use limits::LimitsRect;
 use player_render;
 use policy::*;
 use entity_store::*;
-use observation::shadowcast::ShadowcastEnv;
+use observation::FovEnv;
+use observation::FovCacheTable;
 use knowledge::PlayerKnowledgeGrid;
+use light_grid::LightGrid;
 use spatial_hash::*;
 use renderer::GameRenderer;
+use hud::{Hud, InventoryEntry, InventoryPrompt};
 use input::*;
 use entity_observe;
 use observation::ObservationMetadata;
 use input::GameInput;
+use frame::Frame;
+use projectile_config::ProjectileConfig;
 
 #[derive(Debug)]
 pub enum Error {
@@ -38,6 +43,23 @@ impl From<entity_observe::Error> for Error {
     }
 }
 
+// The door at a coord, if it's open and there's nothing standing in
+// the doorway to stop it closing.
+pub fn closable_door(coord: Vector2<i32>, entity_store: &EntityStore,
+                     spatial_hash: &SpatialHashTable) -> Option<EntityId> {
+    if let Some(cell) = spatial_hash.get(coord) {
+        if !cell.npc_set.is_empty() || cell.player_count > 0 {
+            return None;
+        }
+        if let Some(door_id) = cell.door_set.iter().next() {
+            if entity_store.door_state.get(door_id) == Some(&DoorState::Open) {
+                return Some(*door_id);
+            }
+        }
+    }
+    None
+}
+
 pub struct PlayerActEnv<'a, R: 'a + Rng, Ren: 'a + GameRenderer, Inp: 'a + GameInput> {
     pub renderer: &'a mut Ren,
     pub input: &'a mut Inp,
@@ -46,9 +68,12 @@ pub struct PlayerActEnv<'a, R: 'a + Rng, Ren: 'a + GameRenderer, Inp: 'a + GameI
     pub spatial_hash: &'a mut SpatialHashTable,
     pub entity_id: EntityId,
     pub knowledge: &'a mut PlayerKnowledgeGrid,
-    pub shadowcast: &'a mut ShadowcastEnv,
+    pub fov: &'a mut FovEnv,
+    pub fov_cache: &'a mut FovCacheTable,
+    pub light_grid: &'a mut LightGrid,
     pub time: &'a mut u64,
     pub policy: &'a mut GamePolicy,
+    pub projectile_config: &'a ProjectileConfig,
     pub rng: &'a mut R,
 }
 
@@ -60,11 +85,31 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
             self.spatial_hash,
             *self.time,
             self.knowledge,
-            self.shadowcast,
+            self.fov,
+            self.fov_cache,
+            self.light_grid,
             self.renderer
         )
     }
 
+    fn redraw(&mut self) {
+        self.renderer.update_player_knowledge(self.knowledge, *self.time);
+        self.renderer.clear();
+        self.renderer.draw();
+        self.renderer.publish();
+    }
+
+    // returns true iff the input changed the view
+    fn input_to_view(&mut self, input: InputEvent) -> bool {
+        match input {
+            InputEvent::Resize(width, height) => self.renderer.resize(width, height),
+            InputEvent::Char('-') => self.renderer.zoom(-1),
+            InputEvent::Char('=') => self.renderer.zoom(1),
+            _ => return false,
+        }
+        true
+    }
+
     fn input_to_action(&mut self, input: InputEvent) -> Result<Option<ActionType>> {
         match input {
             InputEvent::Up => return Ok(Some(ActionType::Walk(self.entity_id, Direction::North))),
@@ -74,8 +119,17 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
             InputEvent::Space => return Ok(Some(ActionType::Null)),
             InputEvent::Char('f') => {
                 let start = *self.entity_store.position.get(&self.entity_id).expect("Missing position");
+                // can't fire while swimming
+                if self.spatial_hash.get(start).map(|c| c.deep_water_count > 0).unwrap_or(false) {
+                    return Ok(None);
+                }
+                let weapon_type = match self.equipped_weapon() {
+                    Some(weapon) if weapon.can_fire() => weapon.weapon_type,
+                    _ => return Ok(None),
+                };
                 let action = if let Some(traverse) = self.aim(start)? {
-                    ActionType::FireBullet(traverse)
+                    let projectile = self.projectile_config.projectile(weapon_type.projectile_type());
+                    ActionType::FireWeapon(self.entity_id, projectile, traverse)
                 } else {
                     ActionType::Null
                 };
@@ -86,6 +140,48 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
 
                 return Ok(Some(action));
             }
+            InputEvent::Char('r') => {
+                match self.equipped_weapon() {
+                    Some(weapon) if weapon.can_reload() => return Ok(Some(ActionType::Reload(self.entity_id))),
+                    _ => return Ok(None),
+                }
+            }
+            InputEvent::Char('w') => {
+                if self.entity_store.weapons.contains_key(&self.entity_id) {
+                    return Ok(Some(ActionType::CycleWeapon(self.entity_id)));
+                }
+                return Ok(None);
+            }
+            InputEvent::Char('g') => {
+                let position = *self.entity_store.position.get(&self.entity_id).expect("Missing position");
+                let full = self.entity_store.inventory.get(&self.entity_id).map(|i| i.is_full()).unwrap_or(true);
+                if full {
+                    return Ok(None);
+                }
+                if let Some(item_id) = self.spatial_hash.get(position).and_then(|c| c.item_set.iter().next()) {
+                    return Ok(Some(ActionType::PickUp(self.entity_id, *item_id)));
+                }
+                return Ok(None);
+            }
+            InputEvent::Char('i') => {
+                self.choose_item(InventoryPrompt::View)?;
+                return Ok(None);
+            }
+            InputEvent::Char('u') => {
+                return Ok(self.choose_item(InventoryPrompt::Use)?.map(|item_id| ActionType::UseItem(self.entity_id, item_id)));
+            }
+            InputEvent::Char('d') => {
+                return Ok(self.choose_item(InventoryPrompt::Drop)?.map(|item_id| ActionType::DropItem(self.entity_id, item_id)));
+            }
+            InputEvent::Char('c') => {
+                let start = *self.entity_store.position.get(&self.entity_id).expect("Missing position");
+                let action = if let Some(door_id) = self.choose_door_to_close(start)? {
+                    ActionType::CloseDoor(door_id)
+                } else {
+                    return Ok(None);
+                };
+                return Ok(Some(action));
+            }
             _ => return Ok(None),
         }
     }
@@ -103,6 +199,8 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
             InputEvent::Char('2') => return Some(DebugAction::ChangeVeilMin(0.05)),
             InputEvent::Char('3') => return Some(DebugAction::ChangeVeilMax(-0.05)),
             InputEvent::Char('4') => return Some(DebugAction::ChangeVeilMax(0.05)),
+            InputEvent::Char('6') => return Some(DebugAction::ToggleVeilForecast),
+            InputEvent::Char('7') => return Some(DebugAction::PrintAlertStates),
             InputEvent::Char('8') => return Some(DebugAction::ToggleDiminishingLighting),
             InputEvent::Char('9') => return Some(DebugAction::TogglePlayerOmniscient),
             InputEvent::Char('0') => return Some(DebugAction::Wait),
@@ -116,30 +214,54 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
         }
     }
 
+    fn equipped_weapon(&self) -> Option<Weapon> {
+        self.entity_store.weapons.get(&self.entity_id).and_then(|arsenal| arsenal.equipped()).cloned()
+    }
+
     pub fn act(&mut self) -> Result<MetaAction> {
 
         self.render()?;
+        self.renderer.update_hud(&Hud::new(self.entity_id, self.entity_store));
+        self.redraw();
 
         loop {
+            let input = self.next_input_animating()?;
+
+            let maybe_meta_action = self.input_to_action(input)?.map(MetaAction::Action)
+                .or_else(|| self.input_to_external(input).map(MetaAction::External))
+                .or_else(|| self.input_to_debug(input).map(MetaAction::Debug));
+
+            if let Some(meta_action) = maybe_meta_action {
+                return Ok(meta_action);
+            }
+        }
+    }
+
+    // Advances animations by a frame, committing the changes they make.
+    fn animate_frame(&mut self, frame: Frame) {
+        self.policy.on_frame_animate(frame, self.entity_store, self.spatial_hash, self.rng, self.change);
+        *self.time += 1;
+        self.spatial_hash.update(self.entity_store, self.change, *self.time);
+        self.entity_store.commit_change(self.change);
+    }
 
+    // Waits for the next input which doesn't just change the view,
+    // animating and rendering the level in the meantime.
+    fn next_input_animating(&mut self) -> Result<InputEvent> {
+        loop {
             let event = self.input.next_external();
 
             if let Some(frame) = event.frame() {
-                self.policy.on_frame_animate(frame, self.entity_store, self.spatial_hash, self.rng, self.change);
-                *self.time += 1;
-                self.spatial_hash.update(self.entity_store, self.change, *self.time);
-                self.entity_store.commit_change(self.change);
+                self.animate_frame(frame);
                 self.render()?;
             }
 
             if let Some(input) = event.input() {
-                let maybe_meta_action = self.input_to_action(input)?.map(MetaAction::Action)
-                    .or_else(|| self.input_to_external(input).map(MetaAction::External))
-                    .or_else(|| self.input_to_debug(input).map(MetaAction::Debug));
-
-                if let Some(meta_action) = maybe_meta_action {
-                    return Ok(meta_action);
+                if self.input_to_view(input) {
+                    self.redraw();
+                    continue;
                 }
+                return Ok(input);
             }
         }
     }
@@ -151,10 +273,83 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
             self.spatial_hash,
             *self.time,
             self.knowledge,
-            self.shadowcast
+            self.fov,
+            self.fov_cache,
+            self.light_grid
         ).map_err(Error::ObservationFailed)
     }
 
+    // Picks the only closable door next to the player, or asks which
+    // door to close if there are several.
+    fn choose_door_to_close(&mut self, start: Vector2<i32>) -> Result<Option<EntityId>> {
+        let doors = DirectionsCardinal.into_iter().filter(|d| {
+            closable_door(start + d.vector(), self.entity_store, self.spatial_hash).is_some()
+        }).collect::<Vec<_>>();
+
+        let direction = match doors.len() {
+            0 => return Ok(None),
+            1 => doors[0],
+            _ => {
+                if let Some(direction) = self.choose_direction()? {
+                    direction
+                } else {
+                    return Ok(None);
+                }
+            }
+        };
+
+        Ok(closable_door(start + direction.vector(), self.entity_store, self.spatial_hash))
+    }
+
+    // Shows the inventory screen, returning the item whose letter is
+    // pressed, or None if any other key is pressed.
+    fn choose_item(&mut self, prompt: InventoryPrompt) -> Result<Option<EntityId>> {
+        let items = if let Some(inventory) = self.entity_store.inventory.get(&self.entity_id) {
+            inventory.iter().filter_map(|id| {
+                self.entity_store.item.get(id).map(|item_type| (*id, *item_type))
+            }).collect::<Vec<_>>()
+        } else {
+            return Ok(None);
+        };
+
+        let entries = items.iter().enumerate().map(|(i, &(_, item_type))| {
+            InventoryEntry {
+                letter: (b'a' + i as u8) as char,
+                item_type: item_type,
+            }
+        }).collect::<Vec<_>>();
+
+        self.renderer.show_inventory(prompt, &entries);
+        self.redraw();
+
+        let choice = self.choose_entry(&entries);
+
+        self.renderer.hide_inventory();
+        self.redraw();
+
+        Ok(choice?.map(|i| items[i].0))
+    }
+
+    // Waits for a key, returning the index of the entry with that letter.
+    fn choose_entry(&mut self, entries: &[InventoryEntry]) -> Result<Option<usize>> {
+        let idx = match self.next_input_animating()? {
+            InputEvent::Char(ch) => entries.iter().position(|e| e.letter == ch),
+            _ => None,
+        };
+        Ok(idx)
+    }
+
+    fn choose_direction(&mut self) -> Result<Option<Direction>> {
+        let direction = match self.next_input_animating()? {
+            InputEvent::Up => Some(Direction::North),
+            InputEvent::Down => Some(Direction::South),
+            InputEvent::Left => Some(Direction::West),
+            InputEvent::Right => Some(Direction::East),
+            _ => None,
+        };
+        Ok(direction)
+    }
+
     fn aim(&mut self, start: Vector2<i32>) -> Result<Option<InfiniteAbsoluteLineTraverse>> {
         let mut end = start;
         loop {
@@ -171,14 +366,14 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> PlayerActEnv<'a, R, Ren, Inp
             let event = self.input.next_external();
 
             if let Some(frame) = event.frame() {
-                    self.policy.on_frame_animate(frame, self.entity_store, self.spatial_hash, self.rng, self.change);
-                    *self.time += 1;
-                    self.spatial_hash.update(self.entity_store, self.change, *self.time);
-                    self.entity_store.commit_change(self.change);
-                    self.observe()?;
+                self.animate_frame(frame);
+                self.observe()?;
             }
 
             let change = if let Some(input) = event.input() {
+                if self.input_to_view(input) {
+                    continue;
+                }
                 match input {
                     InputEvent::Up => Vector2::new(0, -1),
                     InputEvent::Down => Vector2::new(0, 1),
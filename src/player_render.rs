@@ -2,8 +2,9 @@ use std::result;
 use entity_store::*;
 use spatial_hash::SpatialHashTable;
 use entity_store::EntityStore;
-use observation::shadowcast;
+use observation::{FovEnv, FovCacheTable};
 use knowledge::PlayerKnowledgeGrid;
+use light_grid::LightGrid;
 use entity_observe;
 use renderer::GameRenderer;
 
@@ -20,7 +21,9 @@ pub fn player_render<Ren: GameRenderer>(
     spatial_hash: &SpatialHashTable,
     time: u64,
     knowledge: &mut PlayerKnowledgeGrid,
-    shadowcast: &mut shadowcast::ShadowcastEnv,
+    fov: &mut FovEnv,
+    fov_cache: &mut FovCacheTable,
+    light_grid: &mut LightGrid,
     renderer: &mut Ren) -> Result<()> {
 
     let metadata = entity_observe::entity_observe(
@@ -29,7 +32,9 @@ pub fn player_render<Ren: GameRenderer>(
         spatial_hash,
         time,
         knowledge,
-        shadowcast
+        fov,
+        fov_cache,
+        light_grid
     ).map_err(Error::ObservationFailed)?;
 
     let player_position = entity_store.position.get(&id)
@@ -1,3 +1,4 @@
+use std::vec;
 use rand::Rng;
 use cgmath::Vector2;
 use entity_store::*;
@@ -7,10 +8,35 @@ use content::*;
 use frame::*;
 use reaction::Reaction;
 use veil_state::VeilState;
+use veil_hazard::VeilHazardConfig;
+use entity_id_allocator::EntityIdAllocator;
+use weather::Weather;
+use direction::{CardinalDirection, Directions};
+
+const SMOKE_DRIFT_DIRECTIONS: [CardinalDirection; 4] = [
+    CardinalDirection::North,
+    CardinalDirection::East,
+    CardinalDirection::South,
+    CardinalDirection::West,
+];
 
 pub struct GamePolicy {
     to_cancel: Vec<EntityId>,
     entities_to_remove: Vec<EntityId>,
+    // (entity, key) pairs where the entity stepped onto the key
+    keys_to_pick_up: Vec<(EntityId, EntityId)>,
+    // (entity, damage) pairs for things hit by projectiles
+    targets_hit: Vec<(EntityId, u32)>,
+    structures_hit: Vec<(EntityId, u32)>,
+    projectiles_to_update: Vec<(EntityId, Projectile)>,
+    trajectories_to_update: Vec<(EntityId, InfiniteAbsoluteLineTraverse)>,
+    messages: Vec<Message>,
+    // (entity, effect, duration) for status effects to be scheduled
+    statuses_to_apply: Vec<(EntityId, StatusEffect, u64)>,
+    // what killed the player, once their hit points run out
+    player_death: Option<Message>,
+    // offset covered by a rain drop's fall, which the wind may tilt
+    rain_delta: Vector2<i32>,
 }
 
 enum RainUpdate {
@@ -24,6 +50,15 @@ impl GamePolicy {
         GamePolicy {
             to_cancel: Vec::new(),
             entities_to_remove: Vec::new(),
+            keys_to_pick_up: Vec::new(),
+            targets_hit: Vec::new(),
+            structures_hit: Vec::new(),
+            projectiles_to_update: Vec::new(),
+            trajectories_to_update: Vec::new(),
+            messages: Vec::new(),
+            statuses_to_apply: Vec::new(),
+            player_death: None,
+            rain_delta: Vector2::new(0, RAIN_LENGTH),
         }
     }
 
@@ -35,8 +70,11 @@ impl GamePolicy {
                 if spatial_hash.contains(new_position) {
                     return Some(RainUpdate::Fall(new_position, new_trajectory));
                 } else {
-                    let wrapped_position = Vector2::new(new_position.x % spatial_hash.width() as i32,
-                                                        new_position.y % spatial_hash.height() as i32);
+                    let width = spatial_hash.width() as i32;
+                    let height = spatial_hash.height() as i32;
+                    // wind can blow rain off either side of the map
+                    let wrapped_position = Vector2::new(((new_position.x % width) + width) % width,
+                                                        ((new_position.y % height) + height) % height);
                     let mut trajectory = new_trajectory.reset_position(wrapped_position);
                     trajectory.step_in_place();
                     return Some(RainUpdate::Fall(wrapped_position, trajectory));
@@ -72,7 +110,7 @@ impl GamePolicy {
                         change.position.insert(*id, new_position);
                         change.finite_trajectory.insert(*id, new_trajectory);
                     }
-                    RainUpdate::Reset(trajectory) => {
+                    RainUpdate::Reset(_) => {
                         if entity_store.splash.contains(id) {
                             change.splash.remove(*id);
                             continue;
@@ -90,7 +128,8 @@ impl GamePolicy {
                             }
                         }
 
-                        let mut new_trajectory = trajectory.reset(new_position);
+                        // start a new fall in the current wind
+                        let mut new_trajectory = FiniteAbsoluteLineTraverse::new_offset(new_position, self.rain_delta);
                         new_trajectory.step_in_place();
                         change.finite_trajectory.insert(*id, new_trajectory);
 
@@ -106,10 +145,16 @@ impl GamePolicy {
 
         for id in entity_store.water.iter() {
             if rng.next_f64() < WATER_TILE_CHANGE_PROBABILITY {
+                let depth = if entity_store.deep_water.contains(id) {
+                    WaterDepth::Deep
+                } else {
+                    WaterDepth::Shallow
+                };
+                let (tile, alternate_tile) = depth.tiles();
                 let tile = if rng.next_f64() < WATER_PROBABILITY {
-                    TileType::Water2
+                    alternate_tile
                 } else {
-                    TileType::Water1
+                    tile
                 };
 
                 if let Some(current_tile) = entity_store.tile.get(id).cloned() {
@@ -122,6 +167,32 @@ impl GamePolicy {
         }
     }
 
+    fn animate_smoke<R: Rng>(&mut self, entity_store: &EntityStore, spatial_hash: &SpatialHashTable,
+                             rng: &mut R, change: &mut EntityStoreChange) {
+
+        for id in entity_store.smoke.iter() {
+            let opacity = entity_store.opacity.get(id).cloned().unwrap_or(0.0) - SMOKE_DECAY;
+            if opacity <= 0.0 {
+                change.remove_entity(*id, entity_store);
+                continue;
+            }
+
+            change.opacity.insert(*id, opacity);
+
+            if rng.next_f64() < SMOKE_DRIFT_PROBABILITY {
+                if let Some(position) = entity_store.position.get(id) {
+                    let direction = SMOKE_DRIFT_DIRECTIONS[rng.gen::<usize>() % SMOKE_DRIFT_DIRECTIONS.len()];
+                    let new_position = position + direction.vector();
+                    if let Some(cell) = spatial_hash.get(new_position) {
+                        if cell.solid_count == 0 {
+                            change.position.insert(*id, new_position);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn on_frame_animate<R: Rng>(&mut self, frame: Frame, entity_store: &EntityStore, spatial_hash: &SpatialHashTable,
                                     rng: &mut R, change: &mut EntityStoreChange) {
 
@@ -132,6 +203,10 @@ impl GamePolicy {
         if frame.id() % WATER_FRAME_RATE == 0 {
             self.animate_water(entity_store, rng, change);
         }
+
+        if frame.id() % SMOKE_FRAME_RATE == 0 {
+            self.animate_smoke(entity_store, spatial_hash, rng, change);
+        }
     }
 
     pub fn has_unresolved_realtime_frames(&mut self, entity_store: &EntityStore) -> bool {
@@ -151,6 +226,15 @@ impl GamePolicy {
                 }
             }
 
+            // projectiles fall once they've travelled their range
+            if let Some(range) = entity_store.projectile_range.get(&id) {
+                if *range == 0 {
+                    change.remove_entity(*id, entity_store);
+                    continue;
+                }
+                change.projectile_range.insert(*id, range - 1);
+            }
+
             if let Some(trajectory) = entity_store.infinite_trajectory.get(&id) {
                 let mut new_trajectory = *trajectory;
                 change.position.insert(*id, new_trajectory.step_in_place());
@@ -159,6 +243,42 @@ impl GamePolicy {
         }
     }
 
+    // The trajectory of a projectile bouncing off the solid cell it was
+    // about to move into. It bounces off whichever walls are in its way,
+    // or straight back if it hit a corner head on.
+    fn ricochet(&self, id: EntityId, destination: Vector2<i32>, entity_store: &EntityStore,
+                spatial_hash: &SpatialHashTable) -> Option<InfiniteAbsoluteLineTraverse> {
+
+        let position = if let Some(position) = entity_store.position.get(&id) {
+            *position
+        } else {
+            return None;
+        };
+
+        let trajectory = if let Some(trajectory) = entity_store.infinite_trajectory.get(&id) {
+            trajectory
+        } else {
+            return None;
+        };
+
+        let step = destination - position;
+        let is_solid = |coord| spatial_hash.get(coord).map(|c| c.solid_count > 0).unwrap_or(true);
+        let blocked_x = step.x != 0 && is_solid(position + Vector2::new(step.x, 0));
+        let blocked_y = step.y != 0 && is_solid(position + Vector2::new(0, step.y));
+
+        let (reflect_x, reflect_y) = if blocked_x == blocked_y {
+            (step.x != 0, step.y != 0)
+        } else {
+            (blocked_x, blocked_y)
+        };
+
+        let mut new_trajectory = trajectory.reflect(position, reflect_x, reflect_y);
+        // skip the current position so the next step leaves it
+        new_trajectory.step_in_place();
+
+        Some(new_trajectory)
+    }
+
     fn handle_collisions(&mut self, entity_store: &EntityStore, spatial_hash: &SpatialHashTable,
                          id: EntityId, position: Vector2<i32>,
                          reactions: &mut Vec<Reaction>) {
@@ -168,7 +288,12 @@ impl GamePolicy {
             // if it's a closed door and we can open doors, open the door instead
             if let Some(door_id) = cell.door_set.iter().next() {
                 if cell.solid_count > 0 && entity_store.door_opener.contains(&id) {
-                    reactions.push(Reaction::immediate(ActionType::OpenDoor(*door_id)));
+                    let lock = entity_store.lock.get(door_id).cloned();
+                    if can_unlock(lock, entity_store.key_ring.get(&id)) {
+                        reactions.push(Reaction::immediate(ActionType::OpenDoor(*door_id)));
+                    } else if entity_store.player.contains(&id) {
+                        self.messages.push(Message::DoorLocked);
+                    }
                     self.to_cancel.push(id);
                     return;
                 }
@@ -181,18 +306,53 @@ impl GamePolicy {
                 }
             }
 
-            if let Some(shootable_id) = cell.shootable_set.iter().next() {
-                if entity_store.bullet.contains(&id) {
-                    self.entities_to_remove.push(*shootable_id);
-                    self.entities_to_remove.push(id);
-                    return;
+            // anything which carries keys picks up keys it walks over
+            if let Some(key_id) = cell.key_set.iter().next() {
+                if entity_store.key_ring.contains_key(&id) {
+                    self.keys_to_pick_up.push((id, *key_id));
                 }
             }
 
-            if cell.solid_count > 0 && entity_store.bullet.contains(&id) {
-                // bullet hit something solid
-                self.entities_to_remove.push(id);
-                return;
+            if let Some(projectile) = entity_store.projectile.get(&id) {
+                if let Some(shootable_id) = cell.shootable_set.iter().next() {
+                    self.targets_hit.push((*shootable_id, projectile.damage));
+                    if projectile.penetration > 0 {
+                        // carry on through the target
+                        self.projectiles_to_update.push((id, Projectile {
+                            penetration: projectile.penetration - 1,
+                            ..*projectile
+                        }));
+                    } else {
+                        self.entities_to_remove.push(id);
+                    }
+                    return;
+                }
+
+                if cell.solid_count > 0 {
+                    // projectile hit something solid, throwing up smoke where it was
+                    if let Some(current_position) = entity_store.position.get(&id) {
+                        reactions.push(Reaction::immediate(ActionType::Smoke(*current_position)));
+                    }
+                    if let Some(structure_id) = cell.integrity_set.iter().next() {
+                        self.structures_hit.push((*structure_id, projectile.damage));
+                    }
+                    let ricochet = if projectile.ricochets > 0 {
+                        self.ricochet(id, position, entity_store, spatial_hash)
+                    } else {
+                        None
+                    };
+                    if let Some(trajectory) = ricochet {
+                        self.projectiles_to_update.push((id, Projectile {
+                            ricochets: projectile.ricochets - 1,
+                            ..*projectile
+                        }));
+                        self.trajectories_to_update.push((id, trajectory));
+                        self.to_cancel.push(id);
+                    } else {
+                        self.entities_to_remove.push(id);
+                    }
+                    return;
+                }
             }
 
             if !cell.npc_set.is_empty() {
@@ -211,17 +371,44 @@ impl GamePolicy {
             }
         } else {
             // destination was outside spatial hash
-            if entity_store.bullet.contains(&id) {
+            if entity_store.projectile.contains_key(&id) {
                 self.entities_to_remove.push(id);
             }
             self.to_cancel.push(id);
         }
     }
 
+    // Npcs right next to a muzzle flash are blinded by it.
+    fn dazzle(&mut self, position: Vector2<i32>, spatial_hash: &SpatialHashTable) {
+        for direction in Directions {
+            if let Some(cell) = spatial_hash.get(position + direction.vector()) {
+                for id in cell.npc_set.iter() {
+                    self.statuses_to_apply.push((*id, StatusEffect::Blinded, BLINDED_DURATION));
+                }
+            }
+        }
+    }
+
     pub fn on_change(&mut self,
                      change: &mut EntityStoreChange, entity_store: &EntityStore, spatial_hash: &SpatialHashTable,
                      reactions: &mut Vec<Reaction>) {
 
+        for (id, flash_change) in change.muzzle_flash.iter() {
+            if let &FlagChangeType::Insert = flash_change {
+                reactions.push(Reaction::new(ActionType::Remove(*id), MUZZLE_FLASH_DURATION));
+                if let Some(position) = post_change_get!(entity_store, change, *id, position) {
+                    self.dazzle(*position, spatial_hash);
+                }
+            }
+        }
+
+        for (id, noise_change) in change.noise.iter() {
+            if let &DataChangeType::Insert(_) = noise_change {
+                // noises are heard as soon as they are made
+                reactions.push(Reaction::immediate(ActionType::Remove(*id)));
+            }
+        }
+
         for (id, position_change) in change.position.iter() {
             if let &DataChangeType::Insert(position) = position_change {
                 if entity_store.collider.contains(id) {
@@ -230,6 +417,58 @@ impl GamePolicy {
             }
         }
 
+        for (id, key_id) in self.keys_to_pick_up.drain(..) {
+            if self.to_cancel.contains(&id) {
+                continue;
+            }
+            if let Some(key) = entity_store.key.get(&key_id) {
+                let mut key_ring = entity_store.key_ring.get(&id).cloned().unwrap_or_default();
+                key_ring.insert(*key);
+                change.key_ring.insert(id, key_ring);
+                self.entities_to_remove.push(key_id);
+                if entity_store.player.contains(&id) {
+                    self.messages.push(Message::KeyPickedUp);
+                }
+            }
+        }
+
+        for (id, damage) in self.targets_hit.drain(..) {
+            if let Some(hit_points) = entity_store.hit_points.get(&id) {
+                let hit_points = hit_points.saturating_sub(damage);
+                if hit_points > 0 {
+                    change.hit_points.insert(id, hit_points);
+                    continue;
+                }
+            }
+            self.entities_to_remove.push(id);
+        }
+
+        for (id, projectile) in self.projectiles_to_update.drain(..) {
+            change.projectile.insert(id, projectile);
+        }
+
+        for (id, trajectory) in self.trajectories_to_update.drain(..) {
+            change.infinite_trajectory.insert(id, trajectory);
+        }
+
+        for (id, damage) in self.structures_hit.drain(..) {
+            if let Some(integrity) = entity_store.integrity.get(&id) {
+                let integrity = integrity.saturating_sub(damage);
+                if let Some(position) = entity_store.position.get(&id) {
+                    if let Some(cell) = spatial_hash.get(*position) {
+                        if integrity == 0 {
+                            Self::destroy_structure(id, cell, change, entity_store);
+                        } else {
+                            change.integrity.insert(id, integrity);
+                            if integrity <= CRACKED_INTEGRITY && cell.cracks_set.is_empty() {
+                                reactions.push(Reaction::immediate(ActionType::Crack(*position)));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
         for id in self.to_cancel.drain(..) {
             change.position.cancel(id);
         }
@@ -239,11 +478,96 @@ impl GamePolicy {
         }
     }
 
+    // Leaves rubble where a structure stood, which can be seen and walked through
+    fn destroy_structure(id: EntityId, cell: &SpatialHashCell,
+                         change: &mut EntityStoreChange, entity_store: &EntityStore) {
+        change.tile.insert(id, ComplexTile::Simple(TileType::Rubble));
+        change.solid.remove(id);
+        change.opacity.remove(id);
+        change.integrity.remove(id);
+        change.door_state.remove(id);
+        change.lock.remove(id);
+
+        for cracks_id in cell.cracks_set.iter() {
+            change.remove_entity(*cracks_id, entity_store);
+        }
+    }
+
+    // Messages for the player produced since this was last called
+    pub fn drain_messages(&mut self) -> vec::Drain<Message> {
+        self.messages.drain(..)
+    }
+
+    // The message explaining the player's death, if they have died
+    pub fn player_death(&self) -> Option<Message> {
+        self.player_death
+    }
+
+    // Status effects to be given to entities since this was last called,
+    // as (entity, effect, duration) triples.
+    pub fn drain_statuses(&mut self) -> vec::Drain<(EntityId, StatusEffect, u64)> {
+        self.statuses_to_apply.drain(..)
+    }
+
+    // Applies the ongoing effects of an entity's surroundings and status
+    // at the start of its turn.
+    pub fn status_update(&mut self, id: EntityId, change: &mut EntityStoreChange,
+                         entity_store: &EntityStore, spatial_hash: &SpatialHashTable) {
+
+        let cell = entity_store.position.get(&id)
+            .and_then(|position| spatial_hash.get(*position));
+        let depth = cell.and_then(WaterDepth::of_cell);
+
+        if depth == Some(WaterDepth::Deep) && entity_store.turn_period.contains_key(&id) {
+            self.statuses_to_apply.push((id, StatusEffect::Slowed, SOAKED_DURATION));
+        }
+
+        // standing in a fire sets entities alight
+        if cell.map(|c| c.fire_count > 0).unwrap_or(false) && entity_store.turn_period.contains_key(&id) {
+            self.statuses_to_apply.push((id, StatusEffect::Burning, BURNING_DURATION));
+        }
+
+        let effects = if let Some(effects) = entity_store.status_effects.get(&id) {
+            effects
+        } else {
+            return;
+        };
+
+        // water puts out fires
+        if effects.contains(StatusEffect::Burning) && depth.is_some() {
+            actions::remove_status(change, entity_store, id, StatusEffect::Burning);
+            return;
+        }
+
+        let damage = effects.damage_per_turn();
+        if damage > 0 {
+            if let Some(hit_points) = entity_store.hit_points.get(&id) {
+                let hit_points = hit_points.saturating_sub(damage);
+                if hit_points == 0 && !entity_store.player.contains(&id) {
+                    change.remove_entity(id, entity_store);
+                } else {
+                    if hit_points == 0 {
+                        self.player_death = Some(Message::BurnedToDeath);
+                    }
+                    change.hit_points.insert(id, hit_points);
+                }
+            }
+        }
+    }
+
     pub fn veil_update(&mut self,
                        change: &mut EntityStoreChange, entity_store: &EntityStore, spatial_hash: &SpatialHashTable,
                        veil_state: &VeilState) {
-        for (sh_cell, veil_cell) in izip!(spatial_hash.iter(), veil_state.iter()) {
+        for (coord, sh_cell, veil_cell) in izip!(veil_state.coord_iter(), spatial_hash.iter(), veil_state.iter()) {
             if let Some(id) = sh_cell.veil_slot_set.iter().next() {
+                let turns_until = veil_state.turns_until(coord);
+                if turns_until != entity_store.veil_forecast.get(id).cloned() {
+                    if let Some(turns_until) = turns_until {
+                        change.veil_forecast.insert(*id, turns_until);
+                    } else {
+                        change.veil_forecast.remove(*id);
+                    }
+                }
                 if veil_cell.current && !entity_store.veil_current.contains(id) {
                     change.veil_current.insert(*id);
                 } else if !veil_cell.current && entity_store.veil_current.contains(id) {
@@ -275,4 +599,144 @@ impl GamePolicy {
             }
         }
     }
+
+    fn veil_spawn_coord<R: Rng>(&self, centre: Vector2<i32>, spatial_hash: &SpatialHashTable,
+                                veil_state: &VeilState, rng: &mut R) -> Option<Vector2<i32>> {
+        let size = (VEIL_SPAWN_RADIUS * 2 + 1) as usize;
+        for _ in 0..VEIL_SPAWN_ATTEMPTS {
+            let offset = Vector2::new((rng.gen::<usize>() % size) as i32 - VEIL_SPAWN_RADIUS,
+                                      (rng.gen::<usize>() % size) as i32 - VEIL_SPAWN_RADIUS);
+            let coord = centre + offset;
+            if !veil_state.get(coord).map(|c| c.current).unwrap_or(false) {
+                continue;
+            }
+            if let Some(cell) = spatial_hash.get(coord) {
+                if !cell.floor_set.is_empty() && cell.solid_count == 0 &&
+                    cell.npc_set.is_empty() && cell.player_count == 0 {
+                    return Some(coord);
+                }
+            }
+        }
+        None
+    }
+
+    // Applies the effects of the veil to an entity about to take its turn.
+    // Returns the id of a veil creature if one was spawned, so it can be
+    // given a turn.
+    pub fn veil_hazards<R: Rng>(&mut self, id: EntityId, config: &VeilHazardConfig,
+                                change: &mut EntityStoreChange, entity_store: &EntityStore,
+                                spatial_hash: &SpatialHashTable, veil_state: &VeilState,
+                                id_allocator: &mut EntityIdAllocator, rng: &mut R) -> Option<EntityId> {
+
+        // entities transformed by the veil are at home in it
+        if entity_store.veil_change.contains(&id) {
+            return None;
+        }
+
+        let rules = if entity_store.player.contains(&id) {
+            &config.player
+        } else if entity_store.npc.contains(&id) {
+            &config.npc
+        } else {
+            return None;
+        };
+
+        let position = if let Some(position) = entity_store.position.get(&id) {
+            *position
+        } else {
+            return None;
+        };
+
+        let in_veil = veil_state.get(position).map(|c| c.current).unwrap_or(false);
+
+        if !in_veil {
+            if entity_store.blurred_vision.contains_key(&id) {
+                change.blurred_vision.remove(id);
+            }
+            return None;
+        }
+
+        if let Some(distance) = rules.blurred_vision_distance {
+            if entity_store.blurred_vision.get(&id) != Some(&distance) {
+                change.blurred_vision.insert(id, distance);
+            }
+        }
+
+        if rules.damage > 0 {
+            self.statuses_to_apply.push((id, StatusEffect::VeilTouched, VEIL_TOUCHED_DURATION));
+            if let Some(hit_points) = entity_store.hit_points.get(&id) {
+                let hit_points = hit_points.saturating_sub(rules.damage);
+                if hit_points == 0 && !entity_store.player.contains(&id) {
+                    change.remove_entity(id, entity_store);
+                    return None;
+                }
+                if hit_points == 0 {
+                    self.player_death = Some(Message::ConsumedByVeil);
+                }
+                change.hit_points.insert(id, hit_points);
+            }
+        }
+
+        if rng.next_f64() < rules.spawn_probability {
+            if let Some(coord) = self.veil_spawn_coord(position, spatial_hash, veil_state, rng) {
+                let spawn_id = id_allocator.allocate();
+                prototypes::undead(change, spawn_id, coord);
+                return Some(spawn_id);
+            }
+        }
+
+        None
+    }
+
+    // Adjusts the amount of rain to match the weather, and limits the
+    // vision of entities out in it.
+    pub fn weather_update<R: Rng>(&mut self, weather: &Weather,
+                                  change: &mut EntityStoreChange, entity_store: &EntityStore,
+                                  spatial_hash: &SpatialHashTable,
+                                  id_allocator: &mut EntityIdAllocator, rng: &mut R) {
+
+        self.rain_delta = weather.rain_delta();
+
+        let num_cells = spatial_hash.width() * spatial_hash.height();
+        let target = (weather.rain_density() * num_cells as f64) as usize;
+        let count = entity_store.rain.len();
+
+        if count < target {
+            for _ in 0..(target - count).min(RAIN_CHANGE_PER_TURN) {
+                let x = (rng.gen::<usize>() % spatial_hash.width()) as i32;
+                let y = (rng.gen::<usize>() % spatial_hash.height()) as i32;
+                prototypes::rain(change, id_allocator.allocate(), Vector2::new(x, y), self.rain_delta, rng);
+            }
+        } else if count > target {
+            for id in entity_store.rain.iter().take((count - target).min(RAIN_CHANGE_PER_TURN)) {
+                change.remove_entity(*id, entity_store);
+            }
+        }
+
+        // opacity changes invalidate cached fields of view, so
+        // heavy rain is only updated when it thickens or thins
+        let heavy_rain_opacity = weather.heavy_rain_opacity();
+        for id in entity_store.heavy_rain.iter() {
+            if entity_store.opacity.get(id) != Some(&heavy_rain_opacity) {
+                change.opacity.insert(*id, heavy_rain_opacity);
+            }
+        }
+
+        let vision_distance = weather.vision_distance();
+        for id in entity_store.vision_distance.keys() {
+            let outside = entity_store.position.get(id)
+                .and_then(|position| spatial_hash.get(*position))
+                .map(|cell| cell.inside_count == 0)
+                .unwrap_or(false);
+
+            let limit = if outside { vision_distance } else { None };
+            if limit != entity_store.weather_vision.get(id).cloned() {
+                if let Some(limit) = limit {
+                    change.weather_vision.insert(*id, limit);
+                } else {
+                    change.weather_vision.remove(*id);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use content::{Projectile, ProjectileType, DEFAULT_PROJECTILE};
+use resources::{self, PROJECTILE_SPEC};
+use simple_file;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileDesc {
+    #[serde(rename = "type")]
+    pub projectile_type: ProjectileType,
+    pub frames_per_step: u64,
+    pub range: u32,
+    pub damage: u32,
+    #[serde(default)]
+    pub penetration: u32,
+    #[serde(default)]
+    pub ricochets: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileSpec {
+    pub projectile: Vec<ProjectileDesc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectileConfig {
+    projectiles: HashMap<ProjectileType, Projectile>,
+}
+
+impl Default for ProjectileConfig {
+    fn default() -> Self {
+        ProjectileConfig {
+            projectiles: HashMap::new(),
+        }
+    }
+}
+
+impl ProjectileConfig {
+    pub fn load() -> Self {
+        simple_file::read_toml::<_, ProjectileSpec>(resources::res_path(PROJECTILE_SPEC))
+            .map(|spec| Self::from_descs(spec.projectile))
+            .expect("Failed to read projectile spec")
+    }
+
+    pub fn from_descs(descs: Vec<ProjectileDesc>) -> Self {
+        let mut config = Self::default();
+        for desc in descs {
+            // Projectiles move on a realtime period of this many frames,
+            // which must be non-zero.
+            assert!(desc.frames_per_step > 0, "Projectile frames_per_step must be non-zero");
+            config.projectiles.insert(desc.projectile_type, Projectile {
+                frames_per_step: desc.frames_per_step,
+                range: desc.range,
+                damage: desc.damage,
+                penetration: desc.penetration,
+                ricochets: desc.ricochets,
+            });
+        }
+        config
+    }
+
+    pub fn projectile(&self, projectile_type: ProjectileType) -> Projectile {
+        self.projectiles.get(&projectile_type).cloned().unwrap_or(DEFAULT_PROJECTILE)
+    }
+}
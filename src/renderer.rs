@@ -1,16 +1,20 @@
 use knowledge::PlayerKnowledgeGrid;
 use render_overlay::RenderOverlay;
+use hud::{Hud, InventoryEntry, InventoryPrompt};
+use content::Message;
 use cgmath::Vector2;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct GameRendererConfig {
     pub diminishing_lighting: bool,
+    pub veil_forecast: bool,
 }
 
 impl Default for GameRendererConfig {
     fn default() -> Self {
         GameRendererConfig {
             diminishing_lighting: true,
+            veil_forecast: false,
         }
     }
 }
@@ -21,7 +25,13 @@ pub trait GameRenderer {
     fn update_player_knowledge(&mut self, knowledge: &PlayerKnowledgeGrid, time: u64);
     fn draw(&mut self);
     fn draw_overlay(&mut self, overlay: RenderOverlay);
+    fn update_hud(&mut self, hud: &Hud);
+    fn show_inventory(&mut self, prompt: InventoryPrompt, entries: &[InventoryEntry]);
+    fn hide_inventory(&mut self);
+    fn show_message(&mut self, message: Message);
     fn publish(&mut self);
     fn set_config(&mut self, config: GameRendererConfig);
     fn config(&self) -> GameRendererConfig;
+    fn resize(&mut self, width_px: u32, height_px: u32);
+    fn zoom(&mut self, steps: i32);
 }
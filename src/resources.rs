@@ -5,6 +5,11 @@ pub const RES_DIR: &'static str = "res";
 
 pub const TILE_SHEET_IMAGE: &'static str = "tiles.png";
 pub const TILE_SHEET_SPEC: &'static str = "tiles.toml";
+pub const VIEWPORT_SPEC: &'static str = "viewport.toml";
+pub const BEHAVIOUR_SPEC: &'static str = "behaviours.toml";
+pub const VEIL_SPEC: &'static str = "veils.toml";
+pub const VEIL_HAZARD_SPEC: &'static str = "veil_hazards.toml";
+pub const PROJECTILE_SPEC: &'static str = "projectiles.toml";
 
 pub fn res_dir() -> PathBuf {
     let mut exe_path = env::current_exe()
@@ -5,6 +5,7 @@ use std::collections::HashSet;
 use entity_store::{EntityId, EntityStore, EntityStoreChange, DataChangeType, FlagChangeType};
 use grid::{static_grid, StaticGridIdx, StaticGrid};
 use limits::LimitsRect;
+use coord::LookupCoord;
 
 #[path = "macros.gen.rs"]
 #[macro_use] mod macros;
@@ -104,3 +105,10 @@ impl LimitsRect for SpatialHashTable {
     fn y_min(&self) -> i32 { self.grid.y_min() }
     fn y_max(&self) -> i32 { self.grid.y_max() }
 }
+
+impl LookupCoord for SpatialHashTable {
+    type Item = SpatialHashCell;
+    fn lookup_coord(&self, coord: Vector2<i32>) -> Option<&Self::Item> {
+        self.get(coord)
+    }
+}
@@ -79,6 +79,13 @@ impl InfiniteRelativeLineTraverse {
         self.reset_in_place();
         self
     }
+
+    pub fn delta(&self) -> Vector2<i32> {
+        let mut delta = Vector2::new(0, 0);
+        self.octant.major_axis.set(&mut delta, self.octant.major_sign as i32 * self.major_delta_abs);
+        self.octant.minor_axis.set(&mut delta, self.octant.minor_sign as i32 * self.minor_delta_abs);
+        delta
+    }
 }
 
 impl Iterator for InfiniteRelativeLineTraverse {
@@ -249,6 +256,19 @@ impl InfiniteAbsoluteLineTraverse {
         self.reset_in_place(start);
         self
     }
+
+    pub fn delta(&self) -> Vector2<i32> {
+        self.relative.delta()
+    }
+
+    // A new line from the given start, with the direction of this line
+    // flipped along the given axes, as if bouncing off a wall.
+    pub fn reflect(&self, start: Vector2<i32>, reflect_x: bool, reflect_y: bool) -> Self {
+        let delta = self.delta();
+        let delta = Vector2::new(if reflect_x { -delta.x } else { delta.x },
+                                 if reflect_y { -delta.y } else { delta.y });
+        Self::new_offset(start, delta)
+    }
 }
 
 impl Iterator for InfiniteAbsoluteLineTraverse {
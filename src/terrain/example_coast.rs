@@ -2,19 +2,28 @@ use rand::Rng;
 use cgmath::Vector2;
 use entity_store::EntityStoreChange;
 use entity_id_allocator::EntityIdAllocator;
-use content::prototypes;
+use content::{prototypes, RAIN_LENGTH};
 use terrain::TerrainMetadata;
-use perlin::{PerlinGrid, PerlinWrapType};
+use perlin::{FractalNoise, FractalConfig, PerlinWrapType};
 
 const WATER_ZOOM: usize = 10;
 const WATER_ZOOM_F: f64 = WATER_ZOOM as f64;
 
+// rougher coastlines than a single octave
+const WATER_NOISE: FractalConfig = FractalConfig {
+    octaves: 3,
+    lacunarity: 2.0,
+    persistence: 0.5,
+    warp_strength: 0.3,
+};
+
 pub fn generate<R: Rng>(width: usize, height: usize,
                         change: &mut EntityStoreChange,
                         allocator: &mut EntityIdAllocator,
                         rng: &mut R) -> TerrainMetadata {
 
-    let water_perlin = PerlinGrid::new(width / WATER_ZOOM, height / WATER_ZOOM, PerlinWrapType::Regenerate, rng);
+    let water_perlin = FractalNoise::new(width / WATER_ZOOM, height / WATER_ZOOM, PerlinWrapType::Regenerate,
+                                         &WATER_NOISE, rng);
 
     let player_id = allocator.allocate();
     prototypes::player(change, player_id, Vector2::new(width as i32 / 2, height as i32 / 2));
@@ -26,7 +35,7 @@ pub fn generate<R: Rng>(width: usize, height: usize,
             if let Some(water_noise) = water_perlin.noise(water_perlin_coord.0, water_perlin_coord.1) {
 
                 if water_noise >= -0.2 && water_noise <= 0.2 {
-                    prototypes::water(change, allocator.allocate(), Vector2::new(x, y), rng);
+                    prototypes::deep_water(change, allocator.allocate(), Vector2::new(x, y), rng);
                     continue;
                 }
             }
@@ -34,7 +43,8 @@ pub fn generate<R: Rng>(width: usize, height: usize,
             prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
 
             if rng.next_f64() < 0.1 {
-                prototypes::rain(change, allocator.allocate(), Vector2::new(x, y), rng);
+                prototypes::rain(change, allocator.allocate(), Vector2::new(x, y),
+                                 Vector2::new(0, RAIN_LENGTH), rng);
             }
         }
     }
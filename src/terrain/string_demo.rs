@@ -2,42 +2,45 @@ use rand::Rng;
 use cgmath::Vector2;
 use entity_store::EntityStoreChange;
 use entity_id_allocator::EntityIdAllocator;
-use content::{prototypes, DoorState};
+use content::{prototypes, DoorState, KeyId, ItemType, WeaponType, RAIN_LENGTH};
 use terrain::TerrainMetadata;
 
+// the key found in the level opens all of its locked doors
+const DEMO_KEY: KeyId = 0;
+
 pub fn generate<R: Rng>(change: &mut EntityStoreChange, allocator: &mut EntityIdAllocator, rng: &mut R) -> TerrainMetadata {
 
     let level_str = vec![
-"#########################.............##########......^^^^^^~~~~~~~~~~~~~~",
-"#,,,,,,,,,,,#,,,,,,,,,,,#.............#,,,,,,,,#......^^^^^^~~~~~~~~~~~~~~",
-"#,,,,,,,,,,,#,,,,,,,,,,,#.............#,,,,,,,,#......^^^^^^^^^^^^^^~~~~~~",
-"#,,,,,,,,,,,#,,,,,,z,,,,#....##########,,,,,,,,#......^^^^^^^^^^^^^^~~~~~~",
-"#,,,,,,,,,,,#,,,,,,,,,,,#....#,,,,,,,,#,,,,,,,,+......^^^^^^~~~~~~~~~~~~~~",
-"########+########+#######....+,,z,,,,,+,,,z,,,,#......^^^^^^~~~~~~~~~~~~~~",
-".............................#,,,,,,,,#,,,,,,,,#......^^^^^^^^^^^^^^~~~~~~",
-".....%%%%%%%%%%%%%%%%%%%.....#,,,,,,,,#,,,,,,,,#......^^^^^^^^^^^^^^~~~~~~",
-".....%,,%,,,%,,,,,,,,,,%.....###################......^^^^^^^^^^^^^^~~~~~~",
-".....%,,%,,,%,,,,,,,,,,%..................................~~~~~~~~~~~~~~~~",
-".....%,,+,,,+,,,,,z,,,,%.....................==..........~~~~~~~~~~~~~~~~~",
-".....%,,%,,,%,,z,,,,,,,%.....................==..@.......~~~~~~~~~~~~~~~~~",
-".....%,,%,,,%,,,,,,,,,,%.....................==............~~~~~~~~~~~~~~~",
-".....%%%%%%%%,,,,,z,,,,+....................................~~~~~~~~~~~~~~",
-".....%,,,,,,%,,z,,z,,,,%.........###+####.##+########........~~~~~~~~~~~~~",
-".....%,,,,,,+,,,,,,,,,,%.........#,,,,,,#.#,,,,#,,,,#.......^^^~~~~~~~~~~~",
-".....%,,,,,,%,,,,,,,,,,%.........#,,,,,,#.#,,,,#,,,,#.......^^^~~~~~~~~~~~",
-".....%,,,,,,%,,,,,,,,,,%.........#,,,,,,#.#,,,,+,,,,+.......^^^~~~~~~~~~~~",
-".....%%%%%%%%%%%%%%%%%%%.........#,,,,,,#.#,,z,#,,,,#.......^^^~~~~~~~~~~~",
-".................................#,,z,,,#.#,,,,#,,,,#.......^^^~~~~~~~~~~~",
-".................................#,,,,,,#.###+#######.......^^^~~~~~~~~~~~",
-".................................#,,,,,,#.#,,,,,,,,,#.......^^^~~~~~~~~~~~",
-".....######+#######.####+###.....#,,,,,,#.#,,,,,,,,,#.......^^^~~~~~~~~~~~",
-".....#,,,,,,,,,,,,#.#,,,,,,#.....#,,,,,,#.#,,,,,,,,,#.......^^^~~~~~~~~~~~",
-".....#,,,,,,,,,,,,#.+,,,z,,#.....#,,,,,,#.#,,,,,,,,,#........~~~~~~~~~~~~~",
-".....#,,,,,,,,,,,,+.#,,,,,,#.....###+####.#####+#####.......~~~~~~~~~~~~~~",
-".....#,,,,,,,,,,,,#.#,,,,,,#................................~~~~~~~~~~~~~~",
-".....##############.####+###...............................~~~~~~~~~~~~~~~",
-"...........................................................~~~~~~~~~~~~~~~",
-"...........................................................~~~~~~~~~~~~~~~",
+"#########################.............##########......^^^^^^www~~~~~~~~~~~",
+"#,,,,,,,,,,,#,,,,,,,,,,,#.............#,,,,,,,,#......^^^^^^www~~~~~~~~~~~",
+"#,,,,,l,,,,,#,,,,,l,,,,,#.............#,,,,,,,,#......^^^^^^^^^^^^^^www~~~",
+"#,,,,,,,,,,,#,,,,,,s,,,,#....##########,,,,l,,,#......^^^^^^^^^^^^^^www~~~",
+"#,,,,,,,,,,,#,,,,,,,,,,,#....#,,,,,,,,#,,,,,,,,+......^^^^^^www~~~~~~~~~~~",
+"########+########+#######....+,,z,,,,,+,,,z,,,,#......^^^^^^www~~~~~~~~~~~",
+".............................#,,,,l,,,#,,,,,,,,#......^^^^^^^^^^^^^^www~~~",
+".....%%%%%%%%%%%%%%%%%%%.....#,,,,,,,,#,,,,,,,,#......^^^^^^^^^^^^^^www~~~",
+".....%,,%,,,%,,,,,,,,,,%.....###################......^^^^^^^^^^^^^^www~~~",
+".....%,,%,,,%,,,,,,,,,,%..rrr...........f.................www~~~~~~~~~~~~~",
+".....%,,+,,,+,,,,,z,,,,%..rrr................==..........www~~~~~~~~~~~~~~",
+".....%,,%,l,%,,z,,,,,,,%..rrr................==..@.......www~~~~~~~~~~~~~~",
+".....%,,%,,,%,,,,,,,,,,%..rrr................==.....K.b.a..www~~~~~~~~~~~~",
+".....%%%%%%%%,,,,,z,,,,+..rrr...............................www~~~~~~~~~~~",
+".....%,,,,,,%,,z,,z,,,,%.........###L####.##+########........www~~~~~~~~~~",
+".....%,,,,,,+,,,,l,,,,,%.........#,,,,,,#.#,,,,#,,,,#.......^^^www~~~~~~~~",
+".....%,,k,,,%,,,,,,,,,,%.........#,,,,,,#.#,,,,#,,l,#.......^^^www~~~~~~~~",
+".....%,,,,,,%,,,,,,,,,,%.........#,,,,,,#.#,,,,+,,,,+.......^^^www~~~~~~~~",
+".....%%%%%%%%%%%%%%%%%%%.........#,,,,,,#.#,,z,#,,,,#.......^^^www~~~~~~~~",
+"ggg..............................#,,z,,,#.#,,,,#,,,,#.......^^^www~~~~~~~~",
+"gggg.............................#,,,,,,#.###+#######.......^^^www~~~~~~~~",
+"ggggg............................#,,,l,,#.#,,,,,,,,,#.......^^^www~~~~~~~~",
+"ggg..######+#######.####+###.....#,,,,,,#.#,,,,,,,,,#.......^^^www~~~~~~~~",
+".....#,,,,,,,,,,,,#.#,,,,,,#.....#,,,,,,#.#,,,,l,,,,#.......^^^www~~~~~~~~",
+".....#,,,,l,,,,,,,#.+,,,z,,#.....#,,,,,,#.#,,,,,,,,,#........www~~~~~~~~~~",
+".....#,,,,,,,,,,,,+.#,,,l,,#.....###L####.#####+#####.......www~~~~~~~~~~~",
+".....#,,,,,,,,,,,,#.#,,,,,,#................................www~~~~~~~~~~~",
+".....##############.####+###...............................www~~~~~~~~~~~~",
+"...........................................................www~~~~~~~~~~~~",
+"...........................................................www~~~~~~~~~~~~",
     ];
 
     let mut pc = 0;
@@ -70,18 +73,60 @@ pub fn generate<R: Rng>(change: &mut EntityStoreChange, allocator: &mut EntityId
                     prototypes::player(change, pc, Vector2::new(x, y));
                     prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
                 }
-                'z' => {
-                    prototypes::undead(change, allocator.allocate(), Vector2::new(x, y));
+                'z' | 's' | 'k' => {
+                    let position = Vector2::new(x, y);
+                    match ch {
+                        's' => prototypes::sentry(change, allocator.allocate(), position),
+                        'k' => prototypes::skulker(change, allocator.allocate(), position),
+                        _ => prototypes::undead(change, allocator.allocate(), position),
+                    }
                     let id = allocator.allocate();
-                    prototypes::stone_floor(change, id, Vector2::new(x, y));
+                    prototypes::stone_floor(change, id, position);
                     change.inside.insert(id);
                 }
                 '+' => {
                     prototypes::door(change, allocator.allocate(), Vector2::new(x, y), DoorState::Closed);
                     prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
                 }
+                'L' => {
+                    prototypes::locked_door(change, allocator.allocate(), Vector2::new(x, y), DEMO_KEY);
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
+                }
+                'K' => {
+                    prototypes::key(change, allocator.allocate(), Vector2::new(x, y), DEMO_KEY);
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
+                }
+                'b' => {
+                    prototypes::item(change, allocator.allocate(), Vector2::new(x, y), ItemType::Bandage);
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
+                }
+                'a' => {
+                    prototypes::item(change, allocator.allocate(), Vector2::new(x, y), ItemType::Ammo(WeaponType::Shotgun));
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
+                }
                 '~' => {
-                    prototypes::water(change, allocator.allocate(), Vector2::new(x, y), rng);
+                    prototypes::deep_water(change, allocator.allocate(), Vector2::new(x, y), rng);
+                }
+                'w' => {
+                    prototypes::shallow_water(change, allocator.allocate(), Vector2::new(x, y), rng);
+                }
+                'l' => {
+                    prototypes::lamp(change, allocator.allocate(), Vector2::new(x, y));
+                    let id = allocator.allocate();
+                    prototypes::stone_floor(change, id, Vector2::new(x, y));
+                    change.inside.insert(id);
+                }
+                'g' => {
+                    prototypes::tall_grass(change, allocator.allocate(), Vector2::new(x, y));
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
+                }
+                'r' => {
+                    prototypes::heavy_rain(change, allocator.allocate(), Vector2::new(x, y));
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
+                }
+                'f' => {
+                    prototypes::fire(change, allocator.allocate(), Vector2::new(x, y));
+                    prototypes::stone_floor(change, allocator.allocate(), Vector2::new(x, y));
                 }
                 '=' => {
                     prototypes::page(change, allocator.allocate(), Vector2::new(x, y));
@@ -91,7 +136,8 @@ pub fn generate<R: Rng>(change: &mut EntityStoreChange, allocator: &mut EntityId
             }
 
             if rng.next_f64() < 0.1 {
-                prototypes::rain(change, allocator.allocate(), Vector2::new(x, y), rng);
+                prototypes::rain(change, allocator.allocate(), Vector2::new(x, y),
+                                 Vector2::new(0, RAIN_LENGTH), rng);
             }
 
             x += 1;
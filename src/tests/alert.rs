@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use grid_search::SearchEnv;
+use observation::shadowcast::{self, ShadowcastEnv};
+use behaviour::{BehaviourEnv, BehaviourState, AlertState};
+use behaviour::search;
+use content::{prototypes, actions, ActionType, ALERT_LOUDNESS};
+use alert;
+use cgmath::Vector2;
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 5;
+const NEAR: u32 = 4;
+const FAR: u32 = 32;
+
+const WALL_X: i32 = 30;
+const PLAYER_COORD: Vector2<i32> = Vector2 { x: 4, y: 2 };
+const SENDER_COORD: Vector2<i32> = Vector2 { x: 6, y: 2 };
+
+struct Env {
+    level: TestLevel,
+    search_env: SearchEnv,
+    shadowcast: ShadowcastEnv,
+    knowledge: HashMap<EntityId, PlayerKnowledgeGrid>,
+    player_id: EntityId,
+    sender_id: EntityId,
+}
+
+impl Env {
+    // A corridor with the player and an npc which can see it, and
+    // a wall to the right with no way through.
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            search_env: SearchEnv::new(WIDTH, HEIGHT),
+            shadowcast: ShadowcastEnv::new(),
+            knowledge: HashMap::new(),
+            player_id: 0,
+            sender_id: 0,
+        };
+
+        for y in 0..HEIGHT as i32 {
+            let id = env.level.allocate();
+            prototypes::wall(&mut env.level.change, id, Vector2::new(WALL_X, y));
+        }
+
+        env.player_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.player_id, PLAYER_COORD);
+
+        env.sender_id = env.add_npc(SENDER_COORD);
+
+        env
+    }
+
+    fn add_npc(&mut self, coord: Vector2<i32>) -> EntityId {
+        let id = self.level.allocate();
+        prototypes::undead(&mut self.level.change, id, coord);
+        self.knowledge.insert(id, PlayerKnowledgeGrid::new(WIDTH, HEIGHT));
+        id
+    }
+
+    fn observe(&mut self, id: EntityId, distance: u32) {
+        let position = *self.level.entity_store.position.get(&id).unwrap();
+        let knowledge = self.knowledge.get_mut(&id).unwrap();
+        shadowcast::observe(&mut self.shadowcast, position, &self.level.spatial_hash, distance,
+                            &self.level.entity_store, self.level.time, knowledge);
+    }
+
+    fn broadcast(&mut self) {
+        alert::broadcast(self.sender_id, &self.level.entity_store, &self.level.spatial_hash,
+                         &mut self.search_env, &mut self.knowledge, self.level.time).unwrap();
+    }
+
+    fn last_player_coord(&self, id: EntityId) -> Option<Vector2<i32>> {
+        self.knowledge.get(&id).unwrap().last_player_coord()
+    }
+}
+
+#[test]
+fn ally_in_earshot_alerted() {
+    let mut env = Env::new();
+    // out of view but within earshot
+    let ally = env.add_npc(SENDER_COORD + Vector2::new(NEAR as i32 + 2, 0));
+    assert!(NEAR + 2 < ALERT_LOUDNESS);
+    env.level.commit();
+    env.observe(env.sender_id, NEAR);
+
+    env.broadcast();
+
+    assert_eq!(env.last_player_coord(ally), Some(PLAYER_COORD));
+}
+
+#[test]
+fn ally_in_view_alerted() {
+    let mut env = Env::new();
+    // out of earshot, but visible down the corridor
+    let ally = env.add_npc(SENDER_COORD + Vector2::new(ALERT_LOUDNESS as i32 + 4, 0));
+    env.level.commit();
+    env.observe(env.sender_id, FAR);
+
+    env.broadcast();
+
+    assert_eq!(env.last_player_coord(ally), Some(PLAYER_COORD));
+}
+
+#[test]
+fn ally_beyond_wall_not_alerted() {
+    let mut env = Env::new();
+    let ally = env.add_npc(Vector2::new(WALL_X + 2, 2));
+    env.level.commit();
+    env.observe(env.sender_id, FAR);
+
+    env.broadcast();
+
+    assert_eq!(env.last_player_coord(ally), None);
+}
+
+#[test]
+fn no_alert_without_sighting() {
+    let mut env = Env::new();
+    let ally = env.add_npc(SENDER_COORD + Vector2::new(1, 0));
+    env.level.commit();
+
+    env.broadcast();
+
+    assert_eq!(env.last_player_coord(ally), None);
+}
+
+#[test]
+fn ally_investigates_visible_report() {
+    let mut env = Env::new();
+    let ally = env.add_npc(SENDER_COORD + Vector2::new(NEAR as i32 + 2, 0));
+    env.level.commit();
+    env.observe(env.sender_id, NEAR);
+    env.broadcast();
+
+    // the player moves out of sight before the ally looks
+    let player_id = env.player_id;
+    env.level.change.remove_entity(player_id, &env.level.entity_store);
+    env.level.commit();
+    env.observe(ally, FAR);
+    assert!(env.knowledge.get(&ally).unwrap().is_visible(PLAYER_COORD, env.level.time));
+
+    let start = *env.level.entity_store.position.get(&ally).unwrap();
+    let mut behaviour_env = BehaviourEnv::new(WIDTH, HEIGHT);
+    let mut state = BehaviourState::new(start);
+    let action = search::search(ally, &env.level.entity_store, env.knowledge.get_mut(&ally).unwrap(),
+                                env.level.time, &mut behaviour_env, &mut state);
+
+    assert_eq!(state.alert, AlertState::Investigating(PLAYER_COORD));
+    if let Some(ActionType::Walk(id, direction)) = action {
+        actions::walk(&mut env.level.change, &env.level.entity_store, id, direction);
+        env.level.commit();
+    } else {
+        panic!("Expected to walk, got {:?}", action);
+    }
+    assert_eq!(*env.level.entity_store.position.get(&ally).unwrap(), start - Vector2::new(1, 0));
+}
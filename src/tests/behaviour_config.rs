@@ -0,0 +1,30 @@
+use toml;
+use behaviour::{BehaviourConfig, BehaviourNode};
+use behaviour::config::BehaviourSpec;
+use content::BehaviourType;
+
+const BEHAVIOUR_SPEC: &'static str = include_str!("../res/behaviours.toml");
+
+fn config() -> BehaviourConfig {
+    let spec: BehaviourSpec = toml::from_str(BEHAVIOUR_SPEC).unwrap();
+    BehaviourConfig::from_descs(spec.behaviour)
+}
+
+#[test]
+fn behaviour_types_differ() {
+    let config = config();
+    assert_eq!(config.nodes(BehaviourType::Undead),
+               &[BehaviourNode::Attack, BehaviourNode::Search, BehaviourNode::Patrol]);
+    assert_eq!(config.nodes(BehaviourType::Sentry),
+               &[BehaviourNode::Attack, BehaviourNode::Search, BehaviourNode::Guard]);
+    assert_eq!(config.nodes(BehaviourType::Skulker),
+               &[BehaviourNode::Flee, BehaviourNode::Wander]);
+}
+
+#[test]
+fn missing_behaviour_types_use_default() {
+    let config = config();
+    assert_eq!(config.nodes(BehaviourType::Player),
+               BehaviourConfig::default().nodes(BehaviourType::Player));
+    assert_eq!(config.nodes(BehaviourType::Player)[0], BehaviourNode::Attack);
+}
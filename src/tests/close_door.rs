@@ -0,0 +1,64 @@
+use entity_store::*;
+use tests::common::TestLevel;
+use player_act::closable_door;
+use content::{prototypes, DoorState};
+use cgmath::Vector2;
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 3;
+const OPEN_DOOR: Vector2<i32> = Vector2 { x: 1, y: 1 };
+const CLOSED_DOOR: Vector2<i32> = Vector2 { x: 3, y: 1 };
+
+struct Env {
+    level: TestLevel,
+    open_door_id: EntityId,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            open_door_id: 0,
+        };
+
+        env.open_door_id = env.level.allocate();
+        prototypes::door(&mut env.level.change, env.open_door_id, OPEN_DOOR, DoorState::Open);
+        let id = env.level.allocate();
+        prototypes::door(&mut env.level.change, id, CLOSED_DOOR, DoorState::Closed);
+        env.level.commit();
+
+        env
+    }
+
+    fn closable_door(&self, coord: Vector2<i32>) -> Option<EntityId> {
+        closable_door(coord, &self.level.entity_store, &self.level.spatial_hash)
+    }
+}
+
+#[test]
+fn open_door_can_be_closed() {
+    let env = Env::new();
+    assert_eq!(env.closable_door(OPEN_DOOR), Some(env.open_door_id));
+}
+
+#[test]
+fn closed_door_and_floor_cannot_be_closed() {
+    let env = Env::new();
+    assert_eq!(env.closable_door(CLOSED_DOOR), None);
+    assert_eq!(env.closable_door(Vector2::new(2, 1)), None);
+}
+
+#[test]
+fn occupied_doorway_cannot_be_closed() {
+    let mut env = Env::new();
+    let id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, id, OPEN_DOOR);
+    env.level.commit();
+
+    assert_eq!(env.closable_door(OPEN_DOOR), None);
+
+    env.level.change.position.insert(id, Vector2::new(0, 1));
+    env.level.commit();
+
+    assert_eq!(env.closable_door(OPEN_DOOR), Some(env.open_door_id));
+}
@@ -0,0 +1,36 @@
+use entity_store::*;
+use spatial_hash::*;
+use entity_id_allocator::EntityIdAllocator;
+
+// The entity store of a level along with its spatial hash, and the
+// change being built up against them, which most tests start from.
+pub struct TestLevel {
+    pub entity_store: EntityStore,
+    pub change: EntityStoreChange,
+    pub spatial_hash: SpatialHashTable,
+    pub id_allocator: EntityIdAllocator,
+    pub time: u64,
+}
+
+impl TestLevel {
+    pub fn new(width: usize, height: usize) -> Self {
+        TestLevel {
+            entity_store: EntityStore::new(),
+            change: EntityStoreChange::new(),
+            spatial_hash: SpatialHashTable::new(width, height),
+            id_allocator: EntityIdAllocator::new(),
+            time: 0,
+        }
+    }
+
+    pub fn allocate(&mut self) -> EntityId {
+        self.id_allocator.allocate()
+    }
+
+    // Commits the change to the store and spatial hash, as of a new time.
+    pub fn commit(&mut self) {
+        self.time += 1;
+        self.spatial_hash.update(&self.entity_store, &self.change, self.time);
+        self.entity_store.commit_change(&mut self.change);
+    }
+}
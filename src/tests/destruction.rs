@@ -0,0 +1,144 @@
+use spatial_hash::*;
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use observation::shadowcast::{self, ShadowcastEnv};
+use policy::GamePolicy;
+use reaction::Reaction;
+use straight_line::InfiniteAbsoluteLineTraverse;
+use content::{prototypes, DoorState, TileType, ComplexTile, DEFAULT_PROJECTILE, WALL_INTEGRITY, DOOR_INTEGRITY,
+              CRACKED_INTEGRITY};
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 3;
+const DISTANCE: u32 = 12;
+const EYE: Vector2<i32> = Vector2 { x: 0, y: 1 };
+const TARGET: Vector2<i32> = Vector2 { x: 5, y: 1 };
+const BEYOND: Vector2<i32> = Vector2 { x: 8, y: 1 };
+
+struct Env {
+    level: TestLevel,
+    policy: GamePolicy,
+    knowledge: PlayerKnowledgeGrid,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            policy: GamePolicy::new(),
+            knowledge: PlayerKnowledgeGrid::new(WIDTH, HEIGHT),
+        };
+
+        for y in 0..HEIGHT as i32 {
+            for x in 0..WIDTH as i32 {
+                let id = env.level.allocate();
+                prototypes::stone_floor(&mut env.level.change, id, Vector2::new(x, y));
+            }
+        }
+        env.level.commit();
+
+        env
+    }
+
+    // Fires a bullet at the target from the cell in front of it,
+    // applying any reactions straight away.
+    fn shoot(&mut self) {
+        let id = self.level.allocate();
+        let traverse = InfiniteAbsoluteLineTraverse::new_between(TARGET - Vector2::new(2, 0), TARGET);
+        prototypes::projectile(&mut self.level.change, id, DEFAULT_PROJECTILE, traverse);
+        self.level.commit();
+
+        self.level.change.position.insert(id, TARGET);
+        let mut reactions = Vec::new();
+        self.policy.on_change(&mut self.level.change, &self.level.entity_store,
+                              &self.level.spatial_hash, &mut reactions);
+        self.level.commit();
+
+        for Reaction { action, .. } in reactions {
+            action.populate(&mut self.level.change, &self.level.entity_store, &mut self.level.id_allocator);
+        }
+        self.level.commit();
+    }
+
+    // shooting throws up smoke which would block the view
+    fn clear_smoke(&mut self) {
+        let ids = self.level.entity_store.smoke.iter().cloned().collect::<Vec<_>>();
+        for id in ids {
+            self.level.change.remove_entity(id, &self.level.entity_store);
+        }
+        self.level.commit();
+    }
+
+    fn observe(&mut self) {
+        shadowcast::observe(&mut ShadowcastEnv::new(), EYE, &self.level.spatial_hash, DISTANCE,
+                            &self.level.entity_store, self.level.time, &mut self.knowledge);
+    }
+
+    fn target_cell(&self) -> &SpatialHashCell {
+        self.level.spatial_hash.get(TARGET).unwrap()
+    }
+}
+
+#[test]
+fn wall_cracks_then_crumbles() {
+    let mut env = Env::new();
+    let wall_id = env.level.allocate();
+    prototypes::wall(&mut env.level.change, wall_id, TARGET);
+    env.level.commit();
+
+    for _ in 0..(WALL_INTEGRITY - CRACKED_INTEGRITY) {
+        env.shoot();
+    }
+    assert_eq!(env.level.entity_store.integrity.get(&wall_id), Some(&CRACKED_INTEGRITY));
+    assert_eq!(env.target_cell().cracks_set.len(), 1);
+    assert!(env.target_cell().solid_count > 0);
+
+    for _ in 0..CRACKED_INTEGRITY {
+        env.shoot();
+    }
+    assert!(env.level.entity_store.integrity.get(&wall_id).is_none());
+    assert_eq!(env.level.entity_store.tile.get(&wall_id), Some(&ComplexTile::Simple(TileType::Rubble)));
+    assert!(env.target_cell().cracks_set.is_empty());
+    assert_eq!(env.target_cell().solid_count, 0);
+    assert_eq!(env.target_cell().opacity_total, 0.0);
+}
+
+#[test]
+fn destroyed_door_leaves_opening() {
+    let mut env = Env::new();
+    let door_id = env.level.allocate();
+    prototypes::door(&mut env.level.change, door_id, TARGET, DoorState::Closed);
+    env.level.commit();
+
+    for _ in 0..DOOR_INTEGRITY {
+        env.shoot();
+    }
+
+    assert!(env.level.entity_store.door_state.get(&door_id).is_none());
+    assert!(env.target_cell().door_set.is_empty());
+    assert_eq!(env.target_cell().solid_count, 0);
+}
+
+#[test]
+fn observers_see_through_opening() {
+    let mut env = Env::new();
+    let wall_id = env.level.allocate();
+    prototypes::wall(&mut env.level.change, wall_id, TARGET);
+    env.level.commit();
+    env.observe();
+
+    assert!(env.knowledge.get(TARGET).unwrap().solid);
+    assert!(!env.knowledge.is_visible(BEYOND, env.level.time));
+
+    for _ in 0..WALL_INTEGRITY {
+        env.shoot();
+    }
+    env.clear_smoke();
+    env.observe();
+
+    let cell = env.knowledge.get(TARGET).unwrap();
+    assert!(!cell.solid);
+    assert!(!cell.wall);
+    assert!(env.knowledge.is_visible(BEYOND, env.level.time));
+}
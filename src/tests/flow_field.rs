@@ -0,0 +1,82 @@
+use tests::common::TestLevel;
+use behaviour::FlowField;
+use content::prototypes;
+use direction::Direction;
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 3;
+
+struct Env {
+    level: TestLevel,
+    flow_field: FlowField,
+}
+
+impl Env {
+    fn new() -> Self {
+        Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            flow_field: FlowField::new(WIDTH, HEIGHT),
+        }
+    }
+
+    fn commit(&mut self) {
+        self.flow_field.invalidate_on_change(&self.level.change, &self.level.entity_store);
+        self.level.commit();
+    }
+}
+
+#[test]
+fn follows_player() {
+    let mut env = Env::new();
+    env.commit();
+
+    let player_coord = Vector2::new(5, 1);
+    assert_eq!(env.flow_field.next_direction(Vector2::new(8, 1), player_coord, &env.level.spatial_hash),
+               Some(Direction::West));
+    assert_eq!(env.flow_field.next_direction(Vector2::new(2, 1), player_coord, &env.level.spatial_hash),
+               Some(Direction::East));
+
+    let player_coord = Vector2::new(5, 2);
+    assert_eq!(env.flow_field.next_direction(Vector2::new(5, 0), player_coord, &env.level.spatial_hash),
+               Some(Direction::South));
+}
+
+#[test]
+fn recomputed_when_walls_change() {
+    let mut env = Env::new();
+    env.commit();
+
+    let player_coord = Vector2::new(0, 1);
+    let npc_coord = Vector2::new(5, 1);
+
+    assert_eq!(env.flow_field.next_direction(npc_coord, player_coord, &env.level.spatial_hash),
+               Some(Direction::West));
+
+    let wall = env.level.allocate();
+    prototypes::wall(&mut env.level.change, wall, Vector2::new(4, 1));
+    env.commit();
+
+    let direction = env.flow_field.next_direction(npc_coord, player_coord, &env.level.spatial_hash);
+    assert!(direction == Some(Direction::North) || direction == Some(Direction::South));
+}
+
+#[test]
+fn avoids_where_the_veil_is_coming() {
+    let mut env = Env::new();
+    env.commit();
+
+    let player_coord = Vector2::new(0, 1);
+    let npc_coord = Vector2::new(5, 1);
+
+    assert_eq!(env.flow_field.next_direction(npc_coord, player_coord, &env.level.spatial_hash),
+               Some(Direction::West));
+
+    let floor = env.level.allocate();
+    prototypes::stone_floor(&mut env.level.change, floor, Vector2::new(4, 1));
+    env.level.change.veil_next.insert(floor);
+    env.commit();
+
+    let direction = env.flow_field.next_direction(npc_coord, player_coord, &env.level.spatial_hash);
+    assert!(direction == Some(Direction::North) || direction == Some(Direction::South));
+}
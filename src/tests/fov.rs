@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+use rand::{Rng, SeedableRng, StdRng};
+use entity_store::*;
+use spatial_hash::*;
+use knowledge::KnowledgeGrid;
+use observation::{ObservationMetadata, FovEnv};
+use content::{prototypes, FovType, TALL_GRASS_OPACITY};
+use cgmath::Vector2;
+
+const WIDTH: usize = 24;
+const HEIGHT: usize = 24;
+const DISTANCE: u32 = 64;
+const WALL_PROBABILITY: f64 = 0.3;
+const NUM_SEEDS: usize = 8;
+const EYES_PER_GRID: usize = 8;
+
+const ALL_FOV_TYPES: [FovType; 3] = [
+    FovType::Shadowcast,
+    FovType::SymmetricShadowcast,
+    FovType::Permissive,
+];
+
+// Records the cells seen at the current time, and which
+// of them were partly obscured.
+struct VisibleSet {
+    visible: HashSet<(i32, i32)>,
+    obscured: HashSet<(i32, i32)>,
+}
+
+impl KnowledgeGrid for VisibleSet {
+    fn update_cell(&mut self, coord: Vector2<i32>, _spatial_hash_cell: &SpatialHashCell,
+                   _entity_store: &EntityStore) -> ObservationMetadata {
+        self.visible.insert((coord.x, coord.y));
+        Default::default()
+    }
+
+    fn update_cell_obscured(&mut self, coord: Vector2<i32>, _spatial_hash_cell: &SpatialHashCell,
+                            _entity_store: &EntityStore, _visibility: f64) -> ObservationMetadata {
+        self.visible.insert((coord.x, coord.y));
+        self.obscured.insert((coord.x, coord.y));
+        Default::default()
+    }
+
+    fn set_time(&mut self, _time: u64) {
+        self.visible.clear();
+        self.obscured.clear();
+    }
+}
+
+struct Env {
+    entity_store: EntityStore,
+    spatial_hash: SpatialHashTable,
+    fov: FovEnv,
+    walls: HashSet<(i32, i32)>,
+}
+
+impl Env {
+    fn new(wall_probability: f64, seed: usize) -> (Self, StdRng) {
+        let mut rng = StdRng::from_seed(&[seed]);
+        let mut walls = HashSet::new();
+
+        for y in 0..HEIGHT as i32 {
+            for x in 0..WIDTH as i32 {
+                if rng.next_f64() < wall_probability {
+                    walls.insert((x, y));
+                }
+            }
+        }
+
+        (Self::with_walls(walls), rng)
+    }
+
+    fn with_walls(walls: HashSet<(i32, i32)>) -> Self {
+        let mut change = EntityStoreChange::new();
+
+        for (id, &(x, y)) in walls.iter().enumerate() {
+            prototypes::wall(&mut change, id as EntityId, Vector2::new(x, y));
+        }
+
+        let mut env = Env {
+            entity_store: EntityStore::new(),
+            spatial_hash: SpatialHashTable::new(WIDTH, HEIGHT),
+            fov: FovEnv::new(),
+            walls: walls,
+        };
+
+        env.spatial_hash.update(&env.entity_store, &change, 1);
+        env.entity_store.commit_change(&mut change);
+
+        env
+    }
+
+    // Fills the columns between min_x and max_x with tall grass.
+    fn grow_grass(&mut self, min_x: i32, max_x: i32) {
+        let mut change = EntityStoreChange::new();
+
+        let mut id = self.walls.len() as EntityId;
+        for y in 0..HEIGHT as i32 {
+            for x in min_x..(max_x + 1) {
+                prototypes::tall_grass(&mut change, id, Vector2::new(x, y));
+                id += 1;
+            }
+        }
+
+        self.spatial_hash.update(&self.entity_store, &change, 2);
+        self.entity_store.commit_change(&mut change);
+    }
+
+    fn observe_all(&mut self, fov_type: FovType, eye: Vector2<i32>) -> VisibleSet {
+        let mut knowledge = VisibleSet { visible: HashSet::new(), obscured: HashSet::new() };
+        self.fov.observe(fov_type, eye, &self.spatial_hash, DISTANCE,
+                         &self.entity_store, 1, &mut knowledge);
+        knowledge
+    }
+
+    fn observe(&mut self, fov_type: FovType, eye: Vector2<i32>) -> HashSet<(i32, i32)> {
+        self.observe_all(fov_type, eye).visible
+    }
+
+    fn random_floor<R: Rng>(&self, rng: &mut R) -> Vector2<i32> {
+        loop {
+            let x = rng.gen::<usize>() % WIDTH;
+            let y = rng.gen::<usize>() % HEIGHT;
+            if !self.walls.contains(&(x as i32, y as i32)) {
+                return Vector2::new(x as i32, y as i32);
+            }
+        }
+    }
+}
+
+// calls f with an environment and an eye, for many random grids and eyes
+fn for_each_random_eye<F: FnMut(&mut Env, Vector2<i32>)>(mut f: F) {
+    for seed in 0..NUM_SEEDS {
+        let (mut env, mut rng) = Env::new(WALL_PROBABILITY, seed);
+        for _ in 0..EYES_PER_GRID {
+            let eye = env.random_floor(&mut rng);
+            f(&mut env, eye);
+        }
+    }
+}
+
+#[test]
+fn eye_is_visible() {
+    for_each_random_eye(|env, eye| {
+        for fov_type in ALL_FOV_TYPES.iter() {
+            assert!(env.observe(*fov_type, eye).contains(&(eye.x, eye.y)), "{:?}", fov_type);
+        }
+    });
+}
+
+#[test]
+fn symmetric_vision_is_mutual() {
+    for_each_random_eye(|env, eye| {
+        let visible = env.observe(FovType::SymmetricShadowcast, eye);
+        for &(x, y) in visible.iter() {
+            if env.walls.contains(&(x, y)) {
+                continue;
+            }
+            let other = env.observe(FovType::SymmetricShadowcast, Vector2::new(x, y));
+            assert!(other.contains(&(eye.x, eye.y)), "{:?} sees {:?} but not vice versa", eye, (x, y));
+        }
+    });
+}
+
+#[test]
+fn permissive_sees_everything_symmetric_sees() {
+    for_each_random_eye(|env, eye| {
+        let symmetric = env.observe(FovType::SymmetricShadowcast, eye);
+        let permissive = env.observe(FovType::Permissive, eye);
+        assert!(symmetric.is_subset(&permissive), "{:?}", eye);
+    });
+}
+
+#[test]
+fn all_agree_without_walls() {
+    let (mut env, mut rng) = Env::new(0.0, 0);
+    for _ in 0..EYES_PER_GRID {
+        let eye = env.random_floor(&mut rng);
+        let shadowcast = env.observe(FovType::Shadowcast, eye);
+        assert_eq!(shadowcast.len(), WIDTH * HEIGHT);
+        for fov_type in ALL_FOV_TYPES.iter() {
+            assert_eq!(env.observe(*fov_type, eye), shadowcast, "{:?}", fov_type);
+        }
+    }
+}
+
+#[test]
+fn permissive_sees_cells_partly_in_view() {
+    // a short wall, with cells beyond its ends only partly in view of the eye
+    let walls = [(4, 2), (4, 3)].iter().cloned().collect();
+    let mut env = Env::with_walls(walls);
+    let eye = Vector2::new(1, 2);
+
+    let symmetric = env.observe(FovType::SymmetricShadowcast, eye);
+    let permissive = env.observe(FovType::Permissive, eye);
+
+    assert!(symmetric.is_subset(&permissive));
+    for coord in [(8, 1), (6, 4), (8, 5)].iter() {
+        assert!(!symmetric.contains(coord), "{:?}", coord);
+        assert!(permissive.contains(coord), "{:?}", coord);
+    }
+}
+
+#[test]
+fn partial_opacity_accumulates() {
+    let mut env = Env::with_walls(HashSet::new());
+    let eye = Vector2::new(1, HEIGHT as i32 / 2);
+
+    // enough grass to block the view, but no single cell of it does
+    let grass_width = (1.0 / TALL_GRASS_OPACITY).ceil() as i32;
+    assert!(TALL_GRASS_OPACITY < 1.0);
+    env.grow_grass(4, 3 + grass_width);
+
+    for fov_type in ALL_FOV_TYPES.iter() {
+        let seen = env.observe_all(*fov_type, eye);
+        assert!(seen.visible.contains(&(3, eye.y)), "{:?}", fov_type);
+        assert!(!seen.obscured.contains(&(3, eye.y)), "{:?}", fov_type);
+        assert!(seen.obscured.contains(&(5, eye.y)), "{:?}", fov_type);
+        assert!(!seen.visible.contains(&(5 + grass_width, eye.y)), "{:?}", fov_type);
+    }
+}
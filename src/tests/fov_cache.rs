@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use entity_store::*;
+use spatial_hash::*;
+use tests::common::TestLevel;
+use knowledge::KnowledgeGrid;
+use observation::{ObservationMetadata, FovCacheTable, FovEnv};
+use content::{prototypes, actions, DoorState, FovType};
+use cgmath::Vector2;
+
+const WIDTH: usize = 12;
+const HEIGHT: usize = 12;
+const DISTANCE: u32 = 10;
+const OBSERVER: EntityId = 1000;
+const DOOR_COORD: Vector2<i32> = Vector2 { x: 6, y: 5 };
+
+// Records the cells seen at the current time.
+struct VisibleSet {
+    visible: HashSet<(i32, i32)>,
+}
+
+impl KnowledgeGrid for VisibleSet {
+    fn update_cell(&mut self, coord: Vector2<i32>, _spatial_hash_cell: &SpatialHashCell,
+                   _entity_store: &EntityStore) -> ObservationMetadata {
+        self.visible.insert((coord.x, coord.y));
+        Default::default()
+    }
+
+    fn set_time(&mut self, _time: u64) {
+        self.visible.clear();
+    }
+}
+
+struct Env {
+    level: TestLevel,
+    fov: FovEnv,
+    fov_cache: FovCacheTable,
+    door_id: EntityId,
+}
+
+impl Env {
+    // a wall with a door in it
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            fov: FovEnv::new(),
+            fov_cache: FovCacheTable::new(),
+            door_id: 0,
+        };
+
+        for y in 0..HEIGHT as i32 {
+            let id = env.level.allocate();
+            let coord = Vector2::new(DOOR_COORD.x, y);
+            if coord == DOOR_COORD {
+                prototypes::door(&mut env.level.change, id, coord, DoorState::Open);
+                env.door_id = id;
+            } else {
+                prototypes::wall(&mut env.level.change, id, coord);
+            }
+        }
+
+        env.level.commit();
+
+        env
+    }
+
+    fn observe_cached(&mut self, eye: Vector2<i32>) -> HashSet<(i32, i32)> {
+        let mut knowledge = VisibleSet { visible: HashSet::new() };
+        self.fov_cache.observe(OBSERVER, &mut self.fov, FovType::Shadowcast, eye, &self.level.spatial_hash,
+                               DISTANCE, &self.level.entity_store, self.level.time, &mut knowledge);
+        knowledge.visible
+    }
+
+    fn observe_uncached(&mut self, eye: Vector2<i32>) -> HashSet<(i32, i32)> {
+        let mut knowledge = VisibleSet { visible: HashSet::new() };
+        self.fov.observe(FovType::Shadowcast, eye, &self.level.spatial_hash, DISTANCE,
+                         &self.level.entity_store, self.level.time, &mut knowledge);
+        knowledge.visible
+    }
+}
+
+#[test]
+fn cached_matches_uncached() {
+    let mut env = Env::new();
+    let eye = Vector2::new(2, 5);
+
+    let first = env.observe_cached(eye);
+    env.level.commit();
+    let second = env.observe_cached(eye);
+
+    assert_eq!(first, env.observe_uncached(eye));
+    assert_eq!(second, first);
+}
+
+#[test]
+fn invalidated_by_opacity_change() {
+    let mut env = Env::new();
+    let eye = Vector2::new(2, 5);
+
+    let open = env.observe_cached(eye);
+    assert!(open.contains(&(8, 5)));
+
+    actions::close_door(&mut env.level.change, &env.level.entity_store, env.door_id, &mut env.level.id_allocator);
+    env.level.commit();
+
+    let closed = env.observe_cached(eye);
+    assert!(!closed.contains(&(8, 5)));
+    assert_eq!(closed, env.observe_uncached(eye));
+}
+
+#[test]
+fn invalidated_by_eye_moving() {
+    let mut env = Env::new();
+
+    env.observe_cached(Vector2::new(2, 5));
+    let moved = env.observe_cached(Vector2::new(2, 2));
+
+    assert_eq!(moved, env.observe_uncached(Vector2::new(2, 2)));
+}
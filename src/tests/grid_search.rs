@@ -0,0 +1,136 @@
+use grid::StaticGrid;
+use grid_search::{astar, SearchEnv, Path, DijkstraMap};
+use direction::{Direction, DirectionsCardinal};
+use coord::LookupCoord;
+use cgmath::Vector2;
+
+// Grid of movement costs, where 0 means a cell can't be entered.
+struct CostGrid(StaticGrid<u32>);
+
+impl CostGrid {
+    fn from_strs(rows: &[&str]) -> Self {
+        let width = rows[0].len();
+        let height = rows.len();
+        CostGrid(StaticGrid::new_call(width, height, |x, y| {
+            match rows[y as usize].as_bytes()[x as usize] {
+                b'#' => 0,
+                b'.' => 1,
+                ch => (ch - b'0') as u32,
+            }
+        }))
+    }
+
+    fn width(&self) -> usize { self.0.width() }
+    fn height(&self) -> usize { self.0.height() }
+}
+
+impl LookupCoord for CostGrid {
+    type Item = u32;
+    fn lookup_coord(&self, coord: Vector2<i32>) -> Option<&Self::Item> {
+        self.0.get(coord)
+    }
+}
+
+fn cost(cell: &u32) -> Option<u32> {
+    if *cell == 0 {
+        None
+    } else {
+        Some(*cell)
+    }
+}
+
+fn path_cost(grid: &CostGrid, path: &Path) -> u32 {
+    path.iter_from(0).map(|step| *grid.lookup_coord(step.to_coord()).unwrap()).sum()
+}
+
+#[test]
+fn astar_finds_shortest_path() {
+    let grid = CostGrid::from_strs(&[
+        ".......",
+        ".#####.",
+        ".......",
+    ]);
+    let mut env = SearchEnv::new(grid.width(), grid.height());
+    let mut path = Path::new();
+
+    astar(&mut env, &grid, Vector2::new(0, 1), DirectionsCardinal,
+          Vector2::new(6, 1), cost, &mut path).unwrap();
+
+    assert_eq!(path.len(), 8);
+    assert_eq!(path.destination(), Some(Vector2::new(6, 1)));
+}
+
+#[test]
+fn astar_avoids_expensive_cells() {
+    let grid = CostGrid::from_strs(&[
+        ".......",
+        ".99999.",
+        ".......",
+        ".......",
+    ]);
+    let mut env = SearchEnv::new(grid.width(), grid.height());
+    let mut path = Path::new();
+
+    astar(&mut env, &grid, Vector2::new(0, 1), DirectionsCardinal,
+          Vector2::new(6, 1), cost, &mut path).unwrap();
+
+    assert_eq!(path_cost(&grid, &path), 8);
+    assert!(path.iter_from(0).all(|step| step.to_coord().y != 1 || step.to_coord().x == 6));
+}
+
+#[test]
+fn astar_no_path() {
+    let grid = CostGrid::from_strs(&[
+        "..#..",
+        "..#..",
+    ]);
+    let mut env = SearchEnv::new(grid.width(), grid.height());
+    let mut path = Path::new();
+
+    assert!(astar(&mut env, &grid, Vector2::new(0, 0), DirectionsCardinal,
+                  Vector2::new(4, 0), cost, &mut path).is_err());
+
+    // the search env can be reused after a failed search
+    astar(&mut env, &grid, Vector2::new(0, 0), DirectionsCardinal,
+          Vector2::new(1, 1), cost, &mut path).unwrap();
+    assert_eq!(path.len(), 2);
+}
+
+#[test]
+fn dijkstra_map_nearest_source() {
+    let grid = CostGrid::from_strs(&[
+        ".........",
+        "....3....",
+        ".........",
+    ]);
+    let mut map = DijkstraMap::new(grid.width(), grid.height());
+
+    map.compute(&grid, vec![Vector2::new(0, 1), Vector2::new(8, 1)], DirectionsCardinal, cost, 100);
+
+    assert_eq!(map.get(Vector2::new(0, 1)), Some(0));
+    assert_eq!(map.get(Vector2::new(2, 1)), Some(2));
+    assert_eq!(map.get(Vector2::new(6, 1)), Some(2));
+    assert_eq!(map.get(Vector2::new(4, 1)), Some(6));
+    assert_eq!(map.get(Vector2::new(4, 0)), Some(5));
+
+    assert_eq!(map.descend(Vector2::new(2, 1), DirectionsCardinal), Some(Direction::West));
+    assert_eq!(map.descend(Vector2::new(6, 1), DirectionsCardinal), Some(Direction::East));
+    assert_eq!(map.descend(Vector2::new(0, 1), DirectionsCardinal), None);
+}
+
+#[test]
+fn dijkstra_map_max_and_recompute() {
+    let grid = CostGrid::from_strs(&[
+        "........",
+    ]);
+    let mut map = DijkstraMap::new(grid.width(), grid.height());
+
+    map.compute(&grid, vec![Vector2::new(0, 0)], DirectionsCardinal, cost, 3);
+    assert_eq!(map.get(Vector2::new(3, 0)), Some(3));
+    assert_eq!(map.get(Vector2::new(4, 0)), None);
+
+    // cells from the previous computation are forgotten
+    map.compute(&grid, vec![Vector2::new(7, 0)], DirectionsCardinal, cost, 3);
+    assert_eq!(map.get(Vector2::new(0, 0)), None);
+    assert_eq!(map.get(Vector2::new(5, 0)), Some(2));
+}
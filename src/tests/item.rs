@@ -0,0 +1,149 @@
+use entity_store::*;
+use tests::common::TestLevel;
+use content::{prototypes, ActionType, ItemType, WeaponType, Arsenal, Weapon,
+              ammo_box_rounds, BANDAGE_HEAL, INVENTORY_CAPACITY, PLAYER_HIT_POINTS};
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+const PLAYER_COORD: Vector2<i32> = Vector2 { x: 5, y: 5 };
+
+struct Env {
+    level: TestLevel,
+    player_id: EntityId,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            player_id: 0,
+        };
+
+        env.player_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.player_id, PLAYER_COORD);
+        env.level.commit();
+
+        env
+    }
+
+    fn act(&mut self, action: ActionType) {
+        action.populate(&mut self.level.change, &self.level.entity_store, &mut self.level.id_allocator);
+        self.level.commit();
+    }
+
+    fn add_item(&mut self, coord: Vector2<i32>, item_type: ItemType) -> EntityId {
+        let id = self.level.allocate();
+        prototypes::item(&mut self.level.change, id, coord, item_type);
+        self.level.commit();
+        id
+    }
+
+    // places an item underfoot and picks it up
+    fn carry(&mut self, item_type: ItemType) -> EntityId {
+        let id = self.add_item(PLAYER_COORD, item_type);
+        let player_id = self.player_id;
+        self.act(ActionType::PickUp(player_id, id));
+        id
+    }
+
+    fn carrying(&self, id: EntityId) -> bool {
+        self.level.entity_store.inventory.get(&self.player_id).unwrap().contains(id)
+    }
+}
+
+#[test]
+fn pick_up_removes_from_level() {
+    let mut env = Env::new();
+    let id = env.carry(ItemType::Bandage);
+
+    assert!(env.carrying(id));
+    assert!(env.level.entity_store.item.contains_key(&id));
+    assert!(env.level.entity_store.position.get(&id).is_none());
+    assert!(env.level.spatial_hash.get(PLAYER_COORD).unwrap().item_set.is_empty());
+}
+
+#[test]
+fn pick_up_only_underfoot() {
+    let mut env = Env::new();
+    let id = env.add_item(PLAYER_COORD + Vector2::new(1, 0), ItemType::Bandage);
+    let player_id = env.player_id;
+    env.act(ActionType::PickUp(player_id, id));
+
+    assert!(!env.carrying(id));
+    assert_eq!(env.level.entity_store.position.get(&id), Some(&(PLAYER_COORD + Vector2::new(1, 0))));
+}
+
+#[test]
+fn full_inventory_refuses_items() {
+    let mut env = Env::new();
+    for _ in 0..INVENTORY_CAPACITY {
+        env.carry(ItemType::Bandage);
+    }
+    let id = env.carry(ItemType::Bandage);
+
+    assert!(!env.carrying(id));
+    assert_eq!(env.level.entity_store.position.get(&id), Some(&PLAYER_COORD));
+}
+
+#[test]
+fn drop_returns_item_to_level() {
+    let mut env = Env::new();
+    let id = env.carry(ItemType::Bandage);
+
+    let destination = Vector2::new(2, 3);
+    env.level.change.position.insert(env.player_id, destination);
+    env.level.commit();
+
+    let player_id = env.player_id;
+    env.act(ActionType::DropItem(player_id, id));
+
+    assert!(!env.carrying(id));
+    assert_eq!(env.level.entity_store.position.get(&id), Some(&destination));
+    assert!(env.level.spatial_hash.get(destination).unwrap().item_set.contains(&id));
+}
+
+#[test]
+fn bandage_heals_and_is_used_up() {
+    let mut env = Env::new();
+    let id = env.carry(ItemType::Bandage);
+    let player_id = env.player_id;
+
+    // no use at full health
+    env.act(ActionType::UseItem(player_id, id));
+    assert!(env.carrying(id));
+
+    env.level.change.hit_points.insert(player_id, 1);
+    env.level.commit();
+    env.act(ActionType::UseItem(player_id, id));
+
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&(1 + BANDAGE_HEAL)));
+    assert!(!env.carrying(id));
+    assert!(!env.level.entity_store.item.contains_key(&id));
+
+    // never heals beyond full health
+    let id = env.carry(ItemType::Bandage);
+    env.level.change.hit_points.insert(player_id, PLAYER_HIT_POINTS - 1);
+    env.level.commit();
+    env.act(ActionType::UseItem(player_id, id));
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&PLAYER_HIT_POINTS));
+}
+
+#[test]
+fn ammo_refills_matching_weapon() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    env.level.change.weapons.insert(player_id, Arsenal::new(vec![Weapon::new(WeaponType::Shotgun, 0)]));
+    env.level.commit();
+
+    let rifle_ammo = env.carry(ItemType::Ammo(WeaponType::Rifle));
+    env.act(ActionType::UseItem(player_id, rifle_ammo));
+    assert!(env.carrying(rifle_ammo));
+
+    let shells = env.carry(ItemType::Ammo(WeaponType::Shotgun));
+    env.act(ActionType::UseItem(player_id, shells));
+    assert!(!env.carrying(shells));
+
+    let weapon = *env.level.entity_store.weapons.get(&player_id).unwrap().equipped().unwrap();
+    assert_eq!(weapon.reserve, ammo_box_rounds(WeaponType::Shotgun));
+}
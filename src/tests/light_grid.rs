@@ -0,0 +1,124 @@
+use tests::common::TestLevel;
+use observation::shadowcast::ShadowcastEnv;
+use observation::{FovEnv, FovCacheTable};
+use knowledge::PlayerKnowledgeGrid;
+use light_grid::LightGrid;
+use entity_observe;
+use content::{prototypes, LAMP_RADIUS, AMBIENT_INSIDE, LIGHT_DIM_THRESHOLD};
+use cgmath::Vector2;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 20;
+
+struct Env {
+    level: TestLevel,
+    shadowcast: ShadowcastEnv,
+    light_grid: LightGrid,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            shadowcast: ShadowcastEnv::new(),
+            light_grid: LightGrid::new(WIDTH, HEIGHT),
+        };
+
+        // everything is inside so there is only dim ambient light
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let id = env.level.allocate();
+                env.level.change.position.insert(id, Vector2::new(x as i32, y as i32));
+                env.level.change.inside.insert(id);
+            }
+        }
+
+        env
+    }
+
+    fn update_light(&mut self) {
+        self.light_grid.update(&mut self.shadowcast, &self.level.spatial_hash, &self.level.entity_store, self.level.time);
+    }
+}
+
+#[test]
+fn dim_without_lights() {
+    let mut env = Env::new();
+    env.level.commit();
+    env.update_light();
+
+    assert_eq!(env.light_grid.intensity(Vector2::new(5, 5)), AMBIENT_INSIDE.intensity());
+}
+
+#[test]
+fn unlit_rooms_seen_within_vision_distance() {
+    let mut env = Env::new();
+    let eye = Vector2::new(2, 10);
+    let id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, id, eye);
+    env.level.commit();
+    assert!(AMBIENT_INSIDE.intensity() >= LIGHT_DIM_THRESHOLD);
+
+    let vision_distance = *env.level.entity_store.vision_distance.get(&id).unwrap() as i32;
+    let mut fov = FovEnv::new();
+    let mut fov_cache = FovCacheTable::new();
+    let mut knowledge = PlayerKnowledgeGrid::new(WIDTH, HEIGHT);
+    entity_observe::entity_observe(id, &env.level.entity_store, &env.level.spatial_hash, env.level.time,
+                                   &mut knowledge, &mut fov, &mut fov_cache, &mut env.light_grid).unwrap();
+
+    assert!(knowledge.is_visible(eye + Vector2::new(vision_distance - 1, 0), env.level.time));
+    assert!(!knowledge.is_visible(eye + Vector2::new(vision_distance + 1, 0), env.level.time));
+}
+
+#[test]
+fn light_falls_off_with_distance() {
+    let mut env = Env::new();
+    let lamp = env.level.allocate();
+    prototypes::lamp(&mut env.level.change, lamp, Vector2::new(10, 10));
+    env.level.commit();
+    env.update_light();
+
+    let at_source = env.light_grid.intensity(Vector2::new(10, 10));
+    let near = env.light_grid.intensity(Vector2::new(12, 10));
+    let far = env.light_grid.intensity(Vector2::new(10 + LAMP_RADIUS as i32 - 1, 10));
+    let outside = env.light_grid.intensity(Vector2::new(10 + LAMP_RADIUS as i32 + 1, 10));
+
+    assert!(at_source > near);
+    assert!(near > far);
+    assert!(far > AMBIENT_INSIDE.intensity());
+    assert_eq!(outside, AMBIENT_INSIDE.intensity());
+}
+
+#[test]
+fn walls_block_light() {
+    let mut env = Env::new();
+    let lamp = env.level.allocate();
+    prototypes::lamp(&mut env.level.change, lamp, Vector2::new(10, 10));
+    for y in 0..HEIGHT {
+        let wall = env.level.allocate();
+        prototypes::wall(&mut env.level.change, wall, Vector2::new(12, y as i32));
+    }
+    env.level.commit();
+    env.update_light();
+
+    assert!(env.light_grid.intensity(Vector2::new(11, 10)) > AMBIENT_INSIDE.intensity());
+    assert!(env.light_grid.intensity(Vector2::new(12, 10)) > AMBIENT_INSIDE.intensity());
+    assert_eq!(env.light_grid.intensity(Vector2::new(13, 10)), AMBIENT_INSIDE.intensity());
+}
+
+#[test]
+fn removed_lights_stop_lighting() {
+    let mut env = Env::new();
+    let lamp = env.level.allocate();
+    prototypes::lamp(&mut env.level.change, lamp, Vector2::new(10, 10));
+    env.level.commit();
+    env.update_light();
+
+    assert!(env.light_grid.intensity(Vector2::new(10, 10)) > AMBIENT_INSIDE.intensity());
+
+    env.level.change.remove_entity(lamp, &env.level.entity_store);
+    env.level.commit();
+    env.update_light();
+
+    assert_eq!(env.light_grid.intensity(Vector2::new(10, 10)), AMBIENT_INSIDE.intensity());
+}
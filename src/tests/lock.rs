@@ -0,0 +1,111 @@
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use observation::shadowcast::{self, ShadowcastEnv};
+use policy::GamePolicy;
+use reaction::Reaction;
+use behaviour::movement_cost;
+use content::{prototypes, actions, ActionType, KeyId, KeyRing, Message};
+use direction::Direction;
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 3;
+const DISTANCE: u32 = 12;
+const LOCK: u32 = 3;
+const PLAYER_COORD: Vector2<i32> = Vector2 { x: 2, y: 1 };
+const DOOR_COORD: Vector2<i32> = Vector2 { x: 3, y: 1 };
+const KEY_COORD: Vector2<i32> = Vector2 { x: 1, y: 1 };
+
+fn key_ring(key: KeyId) -> KeyRing {
+    let mut key_ring = KeyRing::new();
+    key_ring.insert(key);
+    key_ring
+}
+
+struct Env {
+    level: TestLevel,
+    policy: GamePolicy,
+    reactions: Vec<Reaction>,
+    player_id: EntityId,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            policy: GamePolicy::new(),
+            reactions: Vec::new(),
+            player_id: 0,
+        };
+
+        env.player_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.player_id, PLAYER_COORD);
+        let id = env.level.allocate();
+        prototypes::locked_door(&mut env.level.change, id, DOOR_COORD, LOCK);
+        let id = env.level.allocate();
+        prototypes::key(&mut env.level.change, id, KEY_COORD, LOCK);
+        env.level.commit();
+
+        env
+    }
+
+    fn walk(&mut self, direction: Direction) {
+        let player_id = self.player_id;
+        actions::walk(&mut self.level.change, &self.level.entity_store, player_id, direction);
+        self.policy.on_change(&mut self.level.change, &self.level.entity_store, &self.level.spatial_hash,
+                              &mut self.reactions);
+        self.level.commit();
+    }
+
+    fn opening_door(&self) -> bool {
+        self.reactions.iter().any(|r| match r.action {
+            ActionType::OpenDoor(_) => true,
+            _ => false,
+        })
+    }
+
+    fn messages(&mut self) -> Vec<Message> {
+        self.policy.drain_messages().collect()
+    }
+}
+
+#[test]
+fn locked_door_blocks_player_without_key() {
+    let mut env = Env::new();
+    env.walk(Direction::East);
+
+    assert!(!env.opening_door());
+    assert_eq!(env.level.entity_store.position.get(&env.player_id), Some(&PLAYER_COORD));
+    assert_eq!(env.messages(), vec![Message::DoorLocked]);
+}
+
+#[test]
+fn key_picked_up_and_opens_door() {
+    let mut env = Env::new();
+    env.walk(Direction::West);
+
+    assert!(env.level.entity_store.key.is_empty());
+    assert_eq!(env.level.entity_store.key_ring.get(&env.player_id), Some(&key_ring(LOCK)));
+    assert_eq!(env.messages(), vec![Message::KeyPickedUp]);
+
+    env.walk(Direction::East);
+    env.walk(Direction::East);
+
+    assert!(env.opening_door());
+    assert!(env.messages().is_empty());
+}
+
+#[test]
+fn npcs_path_through_locked_doors_only_with_key() {
+    let env = Env::new();
+    let mut knowledge = PlayerKnowledgeGrid::new(WIDTH, HEIGHT);
+    shadowcast::observe(&mut ShadowcastEnv::new(), PLAYER_COORD, &env.level.spatial_hash, DISTANCE,
+                        &env.level.entity_store, env.level.time, &mut knowledge);
+
+    let cell = knowledge.get(DOOR_COORD).unwrap();
+    assert_eq!(cell.lock, Some(LOCK));
+    assert_eq!(movement_cost(cell, None), None);
+    assert_eq!(movement_cost(cell, Some(&key_ring(LOCK + 1))), None);
+    assert!(movement_cost(cell, Some(&key_ring(LOCK))).is_some());
+}
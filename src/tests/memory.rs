@@ -0,0 +1,142 @@
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::{PlayerKnowledgeGrid, KnowledgeGrid, EnemyGhost};
+use observation::shadowcast::{self, ShadowcastEnv};
+use tile::TileResolver;
+use tile_buffer::TileBuffer;
+use content::{prototypes, TileType, GHOST_MAX_AGE, GHOST_TILES, MEMORY_FADE_TIME};
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 3;
+const FAR: u32 = 12;
+const NEAR: u32 = 3;
+const EYE: Vector2<i32> = Vector2 { x: 1, y: 1 };
+const ENEMY_ID: EntityId = 0;
+const ENEMY_START: Vector2<i32> = Vector2 { x: 5, y: 1 };
+
+struct Env {
+    level: TestLevel,
+    shadowcast: ShadowcastEnv,
+    knowledge: PlayerKnowledgeGrid,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            shadowcast: ShadowcastEnv::new(),
+            knowledge: PlayerKnowledgeGrid::new(WIDTH, HEIGHT),
+        };
+
+        prototypes::undead(&mut env.level.change, ENEMY_ID, ENEMY_START);
+        env.level.commit();
+
+        env
+    }
+
+    fn observe(&mut self, distance: u32) {
+        shadowcast::observe(&mut self.shadowcast, EYE, &self.level.spatial_hash, distance,
+                            &self.level.entity_store, self.level.time, &mut self.knowledge);
+    }
+
+    fn ghost(&self) -> Option<EnemyGhost> {
+        self.knowledge.ghosts().find(|&(id, _)| *id == ENEMY_ID).map(|(_, ghost)| *ghost)
+    }
+}
+
+#[test]
+fn ghost_left_when_enemy_out_of_sight() {
+    let mut env = Env::new();
+    env.observe(FAR);
+    let seen_time = env.level.time;
+
+    env.level.change.position.insert(ENEMY_ID, Vector2::new(8, 1));
+    env.level.commit();
+    env.observe(NEAR);
+
+    let ghost = env.ghost().expect("Missing ghost");
+    assert_eq!(ghost.coord, ENEMY_START);
+    assert_eq!(ghost.age(env.level.time), env.level.time - seen_time);
+}
+
+#[test]
+fn ghost_follows_enemy() {
+    let mut env = Env::new();
+    env.observe(FAR);
+
+    env.level.change.position.insert(ENEMY_ID, Vector2::new(8, 1));
+    env.level.commit();
+    env.observe(FAR);
+
+    assert_eq!(env.ghost().expect("Missing ghost").coord, Vector2::new(8, 1));
+}
+
+#[test]
+fn ghost_forgotten_when_seen_empty() {
+    let mut env = Env::new();
+    env.observe(FAR);
+
+    env.level.change.remove_entity(ENEMY_ID, &env.level.entity_store);
+    env.level.commit();
+    env.observe(FAR);
+    env.level.commit();
+    env.observe(FAR);
+
+    assert!(env.ghost().is_none());
+}
+
+#[test]
+fn ghost_forgotten_with_age() {
+    let mut env = Env::new();
+    env.observe(FAR);
+
+    env.knowledge.set_time(env.level.time + GHOST_MAX_AGE - 1);
+    assert!(env.ghost().is_some());
+
+    env.knowledge.set_time(env.level.time + GHOST_MAX_AGE);
+    assert!(env.ghost().is_none());
+}
+
+#[test]
+fn ghost_drawn_in_stale_memory() {
+    let mut env = Env::new();
+    env.observe(FAR);
+
+    env.level.change.position.insert(ENEMY_ID, Vector2::new(8, 1));
+    env.level.commit();
+    env.observe(NEAR);
+
+    let resolver = TileResolver::from_str(include_str!("../res/tiles.toml"));
+    let ghost_sprite = resolver.resolve_tile(TileType::EnemyGhost).channels[0].sprite;
+
+    let mut tile_buffer = TileBuffer::new(WIDTH, HEIGHT);
+    let time = env.level.time + MEMORY_FADE_TIME / 2;
+    tile_buffer.update(Vector2::new(0, 0), &env.knowledge, &resolver, time, false);
+
+    let cell = tile_buffer.get((ENEMY_START.x as usize, ENEMY_START.y as usize)).unwrap();
+    assert!(!cell.visible);
+    assert!(cell.channels.iter().any(|c| *c == Some(ghost_sprite)));
+    assert!(cell.staleness > 0.0 && cell.staleness < 1.0);
+}
+
+#[test]
+fn ghost_fades_with_age() {
+    let mut env = Env::new();
+    env.observe(FAR);
+
+    env.level.change.position.insert(ENEMY_ID, Vector2::new(8, 1));
+    env.level.commit();
+    env.observe(NEAR);
+
+    let resolver = TileResolver::from_str(include_str!("../res/tiles.toml"));
+    let mut tile_buffer = TileBuffer::new(WIDTH, HEIGHT);
+    let coord = (ENEMY_START.x as usize, ENEMY_START.y as usize);
+
+    for (i, tile_type) in GHOST_TILES.iter().enumerate() {
+        let sprite = resolver.resolve_tile(*tile_type).channels[0].sprite;
+        let age = GHOST_MAX_AGE * i as u64 / GHOST_TILES.len() as u64;
+        tile_buffer.update(Vector2::new(0, 0), &env.knowledge, &resolver, env.level.time + age, false);
+        assert!(tile_buffer.get(coord).unwrap().channels.iter().any(|c| *c == Some(sprite)), "{:?}", tile_type);
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod common;
+
 #[cfg(test)]
 mod spatial_hash;
 
@@ -21,3 +24,69 @@ mod perlin;
 
 #[cfg(test)]
 mod veil_state;
+
+#[cfg(test)]
+mod light_grid;
+
+#[cfg(test)]
+mod noise;
+
+#[cfg(test)]
+mod behaviour_config;
+
+#[cfg(test)]
+mod grid_search;
+
+#[cfg(test)]
+mod flow_field;
+
+#[cfg(test)]
+mod fov_cache;
+
+#[cfg(test)]
+mod fov;
+
+#[cfg(test)]
+mod obscurity;
+
+#[cfg(test)]
+mod memory;
+
+#[cfg(test)]
+mod alert;
+
+#[cfg(test)]
+mod veil_hazard;
+
+#[cfg(test)]
+mod veil_config;
+
+#[cfg(test)]
+mod weather;
+
+#[cfg(test)]
+mod water;
+
+#[cfg(test)]
+mod lock;
+
+#[cfg(test)]
+mod close_door;
+
+#[cfg(test)]
+mod destruction;
+
+#[cfg(test)]
+mod projectile;
+
+#[cfg(test)]
+mod weapon;
+
+#[cfg(test)]
+mod item;
+
+#[cfg(test)]
+mod status;
+
+#[cfg(test)]
+mod search;
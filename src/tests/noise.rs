@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use grid_search::SearchEnv;
+use content::{prototypes, DoorState};
+use noise;
+use cgmath::Vector2;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 5;
+
+const WALL_X: i32 = 10;
+const DOOR_COORD: Vector2<i32> = Vector2 { x: WALL_X, y: 2 };
+const NPC_COORD: Vector2<i32> = Vector2 { x: 15, y: 2 };
+const NOISE_COORD: Vector2<i32> = Vector2 { x: 5, y: 2 };
+
+struct Env {
+    level: TestLevel,
+    search_env: SearchEnv,
+    knowledge: HashMap<EntityId, PlayerKnowledgeGrid>,
+    npc_id: EntityId,
+}
+
+impl Env {
+    // Two rooms separated by a wall with a door in it,
+    // with an npc in the right room.
+    fn new(door_state: DoorState) -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            search_env: SearchEnv::new(WIDTH, HEIGHT),
+            knowledge: HashMap::new(),
+            npc_id: 0,
+        };
+
+        for y in 0..HEIGHT as i32 {
+            let id = env.level.allocate();
+            let coord = Vector2::new(WALL_X, y);
+            if coord == DOOR_COORD {
+                prototypes::door(&mut env.level.change, id, coord, door_state);
+            } else {
+                prototypes::wall(&mut env.level.change, id, coord);
+            }
+        }
+
+        env.npc_id = env.level.allocate();
+        prototypes::undead(&mut env.level.change, env.npc_id, NPC_COORD);
+        env.knowledge.insert(env.npc_id, PlayerKnowledgeGrid::new(WIDTH, HEIGHT));
+
+        env.level.commit();
+
+        env
+    }
+
+    fn make_noise(&mut self, loudness: u32) {
+        self.make_muffled_noise(loudness, 0);
+    }
+
+    fn make_muffled_noise(&mut self, loudness: u32, muffling: u32) {
+        let id = self.level.allocate();
+        prototypes::noise(&mut self.level.change, id, NOISE_COORD, loudness);
+        noise::propagate(&self.level.change, &self.level.entity_store, &self.level.spatial_hash,
+                         &mut self.search_env, &mut self.knowledge, muffling).unwrap();
+        self.level.commit();
+    }
+
+    fn heard_coord(&self) -> Option<Vector2<i32>> {
+        self.knowledge.get(&self.npc_id).unwrap().heard_coord()
+    }
+}
+
+#[test]
+fn quiet_noise_heard_through_open_door() {
+    let mut env = Env::new(DoorState::Open);
+    env.make_noise(12);
+    assert_eq!(env.heard_coord(), Some(NOISE_COORD));
+}
+
+#[test]
+fn quiet_noise_blocked_by_closed_door() {
+    let mut env = Env::new(DoorState::Closed);
+    env.make_noise(12);
+    assert_eq!(env.heard_coord(), None);
+}
+
+#[test]
+fn loud_noise_heard_through_closed_door() {
+    let mut env = Env::new(DoorState::Closed);
+    env.make_noise(20);
+    assert_eq!(env.heard_coord(), Some(NOISE_COORD));
+}
+
+#[test]
+fn noise_fades_with_distance() {
+    let mut env = Env::new(DoorState::Open);
+    env.make_noise(4);
+    assert_eq!(env.heard_coord(), None);
+}
+
+#[test]
+fn rain_muffles_noise() {
+    let mut env = Env::new(DoorState::Open);
+    env.make_muffled_noise(12, 4);
+    assert_eq!(env.heard_coord(), None);
+}
@@ -0,0 +1,100 @@
+use rand::{SeedableRng, StdRng};
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use observation::shadowcast::{self, ShadowcastEnv};
+use policy::GamePolicy;
+use frame::Frame;
+use content::{prototypes, SMOKE_FRAME_RATE, SMOKE_INITIAL_OPACITY, SMOKE_DECAY};
+use cgmath::Vector2;
+
+const WIDTH: usize = 12;
+const HEIGHT: usize = 5;
+const DISTANCE: u32 = 10;
+const EYE: Vector2<i32> = Vector2 { x: 1, y: 2 };
+const ENEMY: Vector2<i32> = Vector2 { x: 8, y: 2 };
+const SMOKE: Vector2<i32> = Vector2 { x: 4, y: 2 };
+
+struct Env {
+    level: TestLevel,
+    shadowcast: ShadowcastEnv,
+    knowledge: PlayerKnowledgeGrid,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            shadowcast: ShadowcastEnv::new(),
+            knowledge: PlayerKnowledgeGrid::new(WIDTH, HEIGHT),
+        };
+
+        let id = env.level.allocate();
+        prototypes::undead(&mut env.level.change, id, ENEMY);
+
+        env
+    }
+
+    fn observe(&mut self) {
+        shadowcast::observe(&mut self.shadowcast, EYE, &self.level.spatial_hash, DISTANCE,
+                            &self.level.entity_store, self.level.time, &mut self.knowledge);
+    }
+
+    fn enemy_seen(&self) -> bool {
+        self.knowledge.get(ENEMY).expect("Missing cell").enemy.is_some()
+    }
+}
+
+#[test]
+fn enemy_visible_without_smoke() {
+    let mut env = Env::new();
+    env.level.commit();
+    env.observe();
+
+    assert!(env.enemy_seen());
+    assert!(!env.knowledge.get(ENEMY).unwrap().is_obscured());
+}
+
+#[test]
+fn smoke_hides_enemy() {
+    let mut env = Env::new();
+    for _ in 0..2 {
+        let id = env.level.allocate();
+        prototypes::smoke(&mut env.level.change, id, SMOKE);
+    }
+    env.level.commit();
+    env.observe();
+
+    let cell = env.knowledge.get(ENEMY).unwrap();
+    assert!(cell.is_visible(env.level.time));
+    assert!(cell.is_obscured());
+    assert!(!env.enemy_seen());
+
+    // the smoke itself isn't obscured
+    assert!(!env.knowledge.get(SMOKE).unwrap().is_obscured());
+}
+
+#[test]
+fn enemy_seen_once_smoke_clears() {
+    let mut env = Env::new();
+    for _ in 0..2 {
+        let id = env.level.allocate();
+        prototypes::smoke(&mut env.level.change, id, SMOKE);
+    }
+    env.level.commit();
+    env.observe();
+    assert!(!env.enemy_seen());
+
+    let mut policy = GamePolicy::new();
+    let mut rng = StdRng::from_seed(&[0]);
+    let updates = (SMOKE_INITIAL_OPACITY / SMOKE_DECAY).ceil() as u64 + 1;
+    for i in 0..updates {
+        let frame = Frame::now(i * SMOKE_FRAME_RATE);
+        policy.on_frame_animate(frame, &env.level.entity_store, &env.level.spatial_hash, &mut rng, &mut env.level.change);
+        env.level.commit();
+    }
+
+    assert!(env.level.entity_store.smoke.is_empty());
+
+    env.observe();
+    assert!(env.enemy_seen());
+}
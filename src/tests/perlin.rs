@@ -26,3 +26,48 @@ fn demo() {
         print!("\n");
     }
 }
+
+#[test]
+fn single_octave_matches_perlin_grid() {
+    let mut perlin_rng = StdRng::from_seed(&[1]);
+    let mut fractal_rng = StdRng::from_seed(&[1]);
+    let mut perlin = PerlinGrid::new(WIDTH, HEIGHT, PerlinWrapType::Regenerate, &mut perlin_rng);
+    let mut fractal = FractalNoise::new(WIDTH, HEIGHT, PerlinWrapType::Regenerate,
+                                        &FractalConfig::default(), &mut fractal_rng);
+
+    for _ in 0..20 {
+        for i in 0..(ZOOM * HEIGHT) {
+            for j in 0..(ZOOM * WIDTH) {
+                let (x, y) = (j as f64 / ZOOM_F, i as f64 / ZOOM_F);
+                assert_eq!(perlin.noise(x, y), fractal.noise(x, y));
+            }
+        }
+        perlin.scroll(&mut perlin_rng, 0.3, 0.1);
+        perlin.mutate(&mut perlin_rng, 0.1);
+        fractal.scroll(&mut fractal_rng, 0.3, 0.1);
+        fractal.mutate(&mut fractal_rng, 0.1);
+    }
+}
+
+#[test]
+fn fractal_noise_in_range() {
+    let mut rng = StdRng::from_seed(&[2]);
+    let config = FractalConfig {
+        octaves: 4,
+        lacunarity: 2.0,
+        persistence: 0.5,
+        warp_strength: 0.5,
+    };
+    let mut noise = FractalNoise::new(WIDTH, HEIGHT, PerlinWrapType::Regenerate, &config, &mut rng);
+
+    for _ in 0..20 {
+        for i in 0..(ZOOM * HEIGHT) {
+            for j in 0..(ZOOM * WIDTH) {
+                let value = noise.noise(j as f64 / ZOOM_F, i as f64 / ZOOM_F).expect("Missing noise");
+                assert!(value >= -1.0 && value <= 1.0);
+            }
+        }
+        noise.scroll(&mut rng, -0.3, 0.7);
+        noise.mutate(&mut rng, 0.1);
+    }
+}
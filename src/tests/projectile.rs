@@ -0,0 +1,165 @@
+use toml;
+use entity_store::*;
+use tests::common::TestLevel;
+use policy::GamePolicy;
+use frame::Frame;
+use straight_line::InfiniteAbsoluteLineTraverse;
+use projectile_config::{ProjectileConfig, ProjectileSpec};
+use content::{prototypes, Projectile, ProjectileType, DEFAULT_PROJECTILE};
+use cgmath::Vector2;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 3;
+const START: Vector2<i32> = Vector2 { x: 1, y: 1 };
+const MAX_FRAMES: u64 = 100;
+
+const PROJECTILE_SPEC: &'static str = include_str!("../res/projectiles.toml");
+
+const FAST: Projectile = Projectile {
+    frames_per_step: 1,
+    range: 32,
+    damage: 1,
+    penetration: 0,
+    ricochets: 0,
+};
+
+struct Env {
+    level: TestLevel,
+    policy: GamePolicy,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            policy: GamePolicy::new(),
+        };
+
+        for x in 0..WIDTH as i32 {
+            let id = env.level.allocate();
+            prototypes::stone_wall(&mut env.level.change, id, Vector2::new(x, 0));
+            let id = env.level.allocate();
+            prototypes::stone_wall(&mut env.level.change, id, Vector2::new(x, 2));
+        }
+        env.level.commit();
+
+        env
+    }
+
+    fn add_undead(&mut self, coord: Vector2<i32>) -> EntityId {
+        let id = self.level.allocate();
+        prototypes::undead(&mut self.level.change, id, coord);
+        self.level.commit();
+        id
+    }
+
+    fn fire(&mut self, projectile: Projectile, delta: Vector2<i32>) -> EntityId {
+        let id = self.level.allocate();
+        let traverse = InfiniteAbsoluteLineTraverse::new_offset(START, delta);
+        prototypes::projectile(&mut self.level.change, id, projectile, traverse);
+        self.level.commit();
+        id
+    }
+
+    // Runs frames until the projectile is gone, returning each position it occupied.
+    fn track(&mut self, id: EntityId) -> Vec<Vector2<i32>> {
+        let mut positions = vec![self.level.entity_store.position[&id]];
+        let first_frame = Frame::now(0);
+        for i in 1..MAX_FRAMES {
+            self.policy.on_realtime_change(first_frame, Frame::now(i), &mut self.level.change,
+                                           &self.level.entity_store);
+            let mut reactions = Vec::new();
+            self.policy.on_change(&mut self.level.change, &self.level.entity_store,
+                                  &self.level.spatial_hash, &mut reactions);
+            self.level.commit();
+
+            if let Some(position) = self.level.entity_store.position.get(&id) {
+                positions.push(*position);
+            } else {
+                return positions;
+            }
+        }
+        panic!("Projectile never stopped");
+    }
+}
+
+#[test]
+fn projectile_falls_at_end_of_range() {
+    let mut env = Env::new();
+    let projectile = Projectile { range: 5, ..FAST };
+    let id = env.fire(projectile, Vector2::new(1, 0));
+    let positions = env.track(id);
+
+    assert_eq!(*positions.last().unwrap(), START + Vector2::new(5, 0));
+}
+
+#[test]
+fn projectile_without_penetration_stops_at_first_target() {
+    let mut env = Env::new();
+    let first = env.add_undead(START + Vector2::new(3, 0));
+    let second = env.add_undead(START + Vector2::new(5, 0));
+    let id = env.fire(FAST, Vector2::new(1, 0));
+    env.track(id);
+
+    assert!(!env.level.entity_store.npc.contains(&first));
+    assert!(env.level.entity_store.npc.contains(&second));
+}
+
+#[test]
+fn penetrating_projectile_passes_through_targets() {
+    let mut env = Env::new();
+    let first = env.add_undead(START + Vector2::new(3, 0));
+    let second = env.add_undead(START + Vector2::new(5, 0));
+    let projectile = Projectile { penetration: 1, ..FAST };
+    let id = env.fire(projectile, Vector2::new(1, 0));
+    env.track(id);
+
+    assert!(!env.level.entity_store.npc.contains(&first));
+    assert!(!env.level.entity_store.npc.contains(&second));
+}
+
+#[test]
+fn projectile_ricochets_off_walls() {
+    let mut env = Env::new();
+    let projectile = Projectile { ricochets: 1, range: 6, ..FAST };
+    let id = env.fire(projectile, Vector2::new(4, -1));
+    let positions = env.track(id);
+
+    // it never enters the walls above and below the corridor
+    assert!(positions.iter().all(|p| p.y == 1));
+    assert!(positions.last().unwrap().x > START.x + 3);
+    assert!(positions.windows(2).all(|w| w[1].x >= w[0].x));
+}
+
+#[test]
+fn projectile_bounces_back_from_wall_ahead() {
+    let mut env = Env::new();
+    let wall_id = env.level.allocate();
+    prototypes::stone_wall(&mut env.level.change, wall_id, START + Vector2::new(3, 0));
+    env.level.commit();
+
+    let projectile = Projectile { ricochets: 1, range: 6, ..FAST };
+    let id = env.fire(projectile, Vector2::new(1, 0));
+    let positions = env.track(id);
+
+    assert!(positions.iter().all(|p| p.x < START.x + 3));
+    assert_eq!(positions.iter().map(|p| p.x).max(), Some(START.x + 2));
+    assert_eq!(*positions.last().unwrap(), Vector2::new(0, 1));
+}
+
+#[test]
+fn projectiles_loaded_from_spec() {
+    let spec: ProjectileSpec = toml::from_str(PROJECTILE_SPEC).unwrap();
+    let config = ProjectileConfig::from_descs(spec.projectile);
+    assert_eq!(config.projectile(ProjectileType::Bullet), DEFAULT_PROJECTILE);
+    assert_eq!(ProjectileConfig::default().projectile(ProjectileType::Bullet), DEFAULT_PROJECTILE);
+    assert_eq!(config.projectile(ProjectileType::Pellet).ricochets, 1);
+}
+
+#[test]
+#[should_panic]
+fn projectiles_must_move() {
+    let mut spec: ProjectileSpec = toml::from_str(PROJECTILE_SPEC).unwrap();
+    spec.projectile[0].frames_per_step = 0;
+    ProjectileConfig::from_descs(spec.projectile);
+}
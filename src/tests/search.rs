@@ -0,0 +1,155 @@
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::{PlayerKnowledgeGrid, KnowledgeGrid};
+use observation::{FovEnv, FovCacheTable};
+use light_grid::LightGrid;
+use entity_observe;
+use behaviour::{BehaviourEnv, BehaviourState, AlertState};
+use behaviour::search::{self, SEARCH_TURNS};
+use content::{prototypes, actions, ActionType};
+use cgmath::Vector2;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 5;
+const NPC_COORD: Vector2<i32> = Vector2 { x: 2, y: 2 };
+const LAST_SEEN_COORD: Vector2<i32> = Vector2 { x: 9, y: 2 };
+const OTHER_COORD: Vector2<i32> = Vector2 { x: 14, y: 2 };
+
+struct Env {
+    level: TestLevel,
+    behaviour_env: BehaviourEnv,
+    knowledge: PlayerKnowledgeGrid,
+    state: BehaviourState,
+    npc_id: EntityId,
+}
+
+impl Env {
+    // An npc in an open area, which caught a glimpse of the player at
+    // the last seen position but hasn't seen the place since.
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            behaviour_env: BehaviourEnv::new(WIDTH, HEIGHT),
+            knowledge: PlayerKnowledgeGrid::new(WIDTH, HEIGHT),
+            state: BehaviourState::new(NPC_COORD),
+            npc_id: 0,
+        };
+
+        env.npc_id = env.level.allocate();
+        prototypes::undead(&mut env.level.change, env.npc_id, NPC_COORD);
+        env.glimpse(LAST_SEEN_COORD);
+
+        env
+    }
+
+    // The npc sees the player at a coord, and nothing else, before the
+    // player disappears.
+    fn glimpse(&mut self, coord: Vector2<i32>) {
+        let player_id = self.level.allocate();
+        prototypes::player(&mut self.level.change, player_id, coord);
+        self.level.commit();
+
+        self.knowledge.set_time(self.level.time);
+        self.knowledge.update_cell(coord, self.level.spatial_hash.get(coord).unwrap(), &self.level.entity_store);
+
+        self.level.change.remove_entity(player_id, &self.level.entity_store);
+        self.level.commit();
+    }
+
+    // The npc looks around, as it does at the start of each turn.
+    fn observe(&mut self, fov: &mut FovEnv, fov_cache: &mut FovCacheTable, light_grid: &mut LightGrid) {
+        entity_observe::entity_observe(self.npc_id, &self.level.entity_store, &self.level.spatial_hash,
+                                       self.level.time, &mut self.knowledge, fov, fov_cache, light_grid).unwrap();
+    }
+
+    fn position(&self) -> Vector2<i32> {
+        *self.level.entity_store.position.get(&self.npc_id).unwrap()
+    }
+
+    fn search(&mut self) -> Option<ActionType> {
+        let action = search::search(self.npc_id, &self.level.entity_store, &mut self.knowledge, self.level.time,
+                                    &mut self.behaviour_env, &mut self.state);
+
+        if let Some(ActionType::Walk(id, direction)) = action {
+            actions::walk(&mut self.level.change, &self.level.entity_store, id, direction);
+            self.level.commit();
+        }
+
+        action
+    }
+
+    // Walks until the npc reaches where the player was last seen.
+    fn investigate(&mut self) {
+        let distance = (LAST_SEEN_COORD - NPC_COORD).x;
+        for _ in 0..distance {
+            match self.search() {
+                Some(ActionType::Walk(_, _)) => (),
+                other => panic!("Expected to walk, got {:?}", other),
+            }
+            assert_eq!(self.state.alert, AlertState::Investigating(LAST_SEEN_COORD));
+        }
+    }
+}
+
+#[test]
+fn investigates_last_player_coord() {
+    let mut env = Env::new();
+
+    env.investigate();
+
+    assert_eq!(env.position(), LAST_SEEN_COORD);
+    assert_eq!(env.knowledge.last_player_coord(), Some(LAST_SEEN_COORD));
+}
+
+#[test]
+fn investigates_visible_last_player_coord() {
+    let mut env = Env::new();
+    let mut fov = FovEnv::new();
+    let mut fov_cache = FovCacheTable::new();
+    let mut light_grid = LightGrid::new(WIDTH, HEIGHT);
+
+    // the npc can see that the player has gone, but still goes to look
+    while env.position() != LAST_SEEN_COORD {
+        env.observe(&mut fov, &mut fov_cache, &mut light_grid);
+        assert!(env.knowledge.is_visible(LAST_SEEN_COORD, env.level.time));
+
+        match env.search() {
+            Some(ActionType::Walk(_, _)) => (),
+            other => panic!("Expected to walk, got {:?}", other),
+        }
+        assert_eq!(env.state.alert, AlertState::Investigating(LAST_SEEN_COORD));
+    }
+
+    env.observe(&mut fov, &mut fov_cache, &mut light_grid);
+    env.search();
+    assert_eq!(env.state.alert, AlertState::Searching { origin: LAST_SEEN_COORD, turns_remaining: SEARCH_TURNS - 1 });
+}
+
+#[test]
+fn searches_then_returns_to_patrol() {
+    let mut env = Env::new();
+    env.investigate();
+
+    // the player isn't there, so the npc starts searching
+    for turn in 1..(SEARCH_TURNS + 1) {
+        env.search();
+        assert_eq!(env.state.alert, AlertState::Searching { origin: LAST_SEEN_COORD, turns_remaining: SEARCH_TURNS - turn });
+        assert_eq!(env.knowledge.last_player_coord(), None);
+    }
+
+    assert!(env.search().is_none());
+    assert_eq!(env.state.alert, AlertState::Patrolling);
+}
+
+#[test]
+fn new_sighting_interrupts_search() {
+    let mut env = Env::new();
+    env.investigate();
+    env.search();
+    assert_eq!(env.state.alert, AlertState::Searching { origin: LAST_SEEN_COORD, turns_remaining: SEARCH_TURNS - 1 });
+
+    env.glimpse(OTHER_COORD);
+    env.search();
+
+    assert_eq!(env.state.alert, AlertState::Investigating(OTHER_COORD));
+}
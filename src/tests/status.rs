@@ -0,0 +1,240 @@
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use observation::{FovEnv, FovCacheTable};
+use light_grid::LightGrid;
+use level_env::TurnEvent;
+use schedule::Schedule;
+use policy::GamePolicy;
+use reaction::Reaction;
+use entity_observe;
+use rand::{SeedableRng, StdRng};
+use content::{prototypes, ActionType, Message, StatusEffect, StatusEffects, SOAKED_DURATION,
+              BLINDED_VISION_DISTANCE, BLINDED_DURATION, BURNING_DAMAGE, BURNING_DURATION,
+              PLAYER_HIT_POINTS};
+use cgmath::Vector2;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 3;
+const PLAYER_COORD: Vector2<i32> = Vector2 { x: 1, y: 1 };
+const ENEMY_COORD: Vector2<i32> = Vector2 { x: 8, y: 1 };
+
+struct Env {
+    level: TestLevel,
+    policy: GamePolicy,
+    schedule: Schedule<TurnEvent>,
+    player_id: EntityId,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            policy: GamePolicy::new(),
+            schedule: Schedule::new(),
+            player_id: 0,
+        };
+
+        env.player_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.player_id, PLAYER_COORD);
+        env.level.commit();
+
+        env
+    }
+
+    // gives the player an effect, scheduling it to wear off as the game loop does
+    fn apply(&mut self, effect: StatusEffect, duration: u64) {
+        let expiry = self.schedule.absolute_time() + duration;
+        let mut effects = self.level.entity_store.status_effects.get(&self.player_id).cloned().unwrap_or_default();
+        effects.insert(effect, expiry);
+        self.level.change.status_effects.insert(self.player_id, effects);
+        self.level.commit();
+        let action = ActionType::ExpireStatus(self.player_id, effect, expiry);
+        self.schedule.insert(TurnEvent::Action(action), duration);
+    }
+
+    // runs the next scheduled event, returning its time
+    fn run_next(&mut self) -> u64 {
+        let entry = self.schedule.next().expect("Empty schedule");
+        if let TurnEvent::Action(action) = entry.value {
+            action.populate(&mut self.level.change, &self.level.entity_store, &mut self.level.id_allocator);
+            self.level.commit();
+        }
+        entry.release_time
+    }
+
+    fn effects(&self) -> Option<&StatusEffects> {
+        self.level.entity_store.status_effects.get(&self.player_id)
+    }
+
+    fn status_update(&mut self) {
+        let player_id = self.player_id;
+        self.policy.status_update(player_id, &mut self.level.change, &self.level.entity_store,
+                                  &self.level.spatial_hash);
+        self.level.commit();
+    }
+}
+
+#[test]
+fn effects_combine() {
+    let mut effects = StatusEffects::new();
+    effects.insert(StatusEffect::Slowed, 4);
+    effects.insert(StatusEffect::VeilTouched, 4);
+    effects.insert(StatusEffect::Blinded, 4);
+
+    assert_eq!(effects.turn_penalty(),
+               StatusEffect::Slowed.turn_penalty() + StatusEffect::VeilTouched.turn_penalty());
+    assert_eq!(effects.vision_limit(), Some(BLINDED_VISION_DISTANCE));
+    assert_eq!(effects.damage_per_turn(), 0);
+
+    // reapplying never shortens an effect
+    effects.insert(StatusEffect::Slowed, 2);
+    assert_eq!(effects.expiry(StatusEffect::Slowed), Some(4));
+}
+
+#[test]
+fn effect_wears_off_when_scheduled() {
+    let mut env = Env::new();
+    env.apply(StatusEffect::Slowed, 3);
+    assert!(env.effects().unwrap().contains(StatusEffect::Slowed));
+
+    assert_eq!(env.run_next(), 3);
+    assert!(env.effects().is_none());
+}
+
+#[test]
+fn reapplied_effect_outlasts_first_expiry() {
+    let mut env = Env::new();
+    env.apply(StatusEffect::Blinded, 2);
+    env.apply(StatusEffect::Blinded, 5);
+
+    env.run_next();
+    assert!(env.effects().unwrap().contains(StatusEffect::Blinded));
+
+    env.run_next();
+    assert!(env.effects().is_none());
+}
+
+#[test]
+fn expiry_after_removal_is_harmless() {
+    let mut env = Env::new();
+    env.apply(StatusEffect::Slowed, 2);
+    let player_id = env.player_id;
+    env.level.change.remove_entity(player_id, &env.level.entity_store);
+    env.level.commit();
+
+    env.run_next();
+    assert!(env.level.entity_store.status_effects.is_empty());
+}
+
+#[test]
+fn blindness_limits_vision() {
+    let mut env = Env::new();
+    let id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, id, ENEMY_COORD);
+    env.level.commit();
+
+    let mut fov = FovEnv::new();
+    let mut fov_cache = FovCacheTable::new();
+    let mut light_grid = LightGrid::new(WIDTH, HEIGHT);
+
+    let mut knowledge = PlayerKnowledgeGrid::new(WIDTH, HEIGHT);
+    entity_observe::entity_observe(env.player_id, &env.level.entity_store, &env.level.spatial_hash, env.level.time,
+                                   &mut knowledge, &mut fov, &mut fov_cache, &mut light_grid).unwrap();
+    assert_eq!(knowledge.get(ENEMY_COORD).unwrap().enemy, Some(id));
+
+    env.apply(StatusEffect::Blinded, 2);
+    let mut knowledge = PlayerKnowledgeGrid::new(WIDTH, HEIGHT);
+    entity_observe::entity_observe(env.player_id, &env.level.entity_store, &env.level.spatial_hash, env.level.time,
+                                   &mut knowledge, &mut fov, &mut fov_cache, &mut light_grid).unwrap();
+    assert!(knowledge.get(ENEMY_COORD).unwrap().enemy.is_none());
+}
+
+#[test]
+fn burning_hurts_each_turn() {
+    let mut env = Env::new();
+    env.apply(StatusEffect::Burning, 10);
+    env.status_update();
+    env.status_update();
+
+    let player_id = env.player_id;
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&(PLAYER_HIT_POINTS - 2 * BURNING_DAMAGE)));
+}
+
+#[test]
+fn burning_to_death_is_reported() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    env.level.change.hit_points.insert(player_id, BURNING_DAMAGE);
+    env.level.commit();
+
+    env.apply(StatusEffect::Burning, 10);
+    assert_eq!(env.policy.player_death(), None);
+    env.status_update();
+
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&0));
+    assert_eq!(env.policy.player_death(), Some(Message::BurnedToDeath));
+}
+
+#[test]
+fn water_puts_out_fire() {
+    let mut env = Env::new();
+    let mut rng = StdRng::from_seed(&[0]);
+    let id = env.level.allocate();
+    prototypes::shallow_water(&mut env.level.change, id, PLAYER_COORD, &mut rng);
+    env.level.commit();
+
+    env.apply(StatusEffect::Burning, 10);
+    env.status_update();
+
+    let player_id = env.player_id;
+    assert!(env.effects().is_none());
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&PLAYER_HIT_POINTS));
+}
+
+#[test]
+fn swimming_slows() {
+    let mut env = Env::new();
+    let mut rng = StdRng::from_seed(&[0]);
+    let id = env.level.allocate();
+    prototypes::deep_water(&mut env.level.change, id, PLAYER_COORD, &mut rng);
+    env.level.commit();
+
+    env.status_update();
+
+    let player_id = env.player_id;
+    let statuses = env.policy.drain_statuses().collect::<Vec<_>>();
+    assert_eq!(statuses, vec![(player_id, StatusEffect::Slowed, SOAKED_DURATION)]);
+}
+
+#[test]
+fn standing_in_fire_sets_alight() {
+    let mut env = Env::new();
+    let id = env.level.allocate();
+    prototypes::fire(&mut env.level.change, id, PLAYER_COORD);
+    env.level.commit();
+
+    env.status_update();
+
+    let player_id = env.player_id;
+    let statuses = env.policy.drain_statuses().collect::<Vec<_>>();
+    assert_eq!(statuses, vec![(player_id, StatusEffect::Burning, BURNING_DURATION)]);
+}
+
+#[test]
+fn point_blank_muzzle_flash_blinds() {
+    let mut env = Env::new();
+    let near_id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, near_id, PLAYER_COORD + Vector2::new(1, 0));
+    let far_id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, far_id, ENEMY_COORD);
+    env.level.commit();
+
+    let flash_id = env.level.allocate();
+    prototypes::muzzle_flash(&mut env.level.change, flash_id, PLAYER_COORD);
+    let mut reactions = Vec::<Reaction>::new();
+    env.policy.on_change(&mut env.level.change, &env.level.entity_store, &env.level.spatial_hash, &mut reactions);
+
+    let statuses = env.policy.drain_statuses().collect::<Vec<_>>();
+    assert_eq!(statuses, vec![(near_id, StatusEffect::Blinded, BLINDED_DURATION)]);
+}
@@ -48,3 +48,16 @@ fn empty_line() {
     assert_eq!(end, Vector2::new(1, 1));
     assert_eq!(traverse.next(), None);
 }
+
+#[test]
+fn infinite_absolute_reflect() {
+    let traverse = InfiniteAbsoluteLineTraverse::new_between(Vector2::new(0, 0), Vector2::new(3, -1));
+    assert_eq!(traverse.delta(), Vector2::new(3, -1));
+
+    let mut reflected = traverse.reflect(Vector2::new(5, 5), false, true);
+    assert_eq!(reflected.delta(), Vector2::new(3, 1));
+    assert_eq!(reflected.next(), Some(Vector2::new(5, 5)));
+
+    let reflected = traverse.reflect(Vector2::new(5, 5), true, true);
+    assert_eq!(reflected.delta(), Vector2::new(-3, 1));
+}
@@ -0,0 +1,64 @@
+use toml;
+use rand::{StdRng, SeedableRng};
+use veil_config::{VeilConfig, VeilSpec, VeilEscalation};
+use veil_state::VeilState;
+use content::VeilStepInfo;
+
+const VEIL_SPEC: &'static str = include_str!("../res/veils.toml");
+
+fn escalating_config() -> VeilConfig {
+    let mut config = VeilConfig::default();
+    config.step = VeilStepInfo::default();
+    config.escalation.push(VeilEscalation {
+        every: 10,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        min: -1.0,
+        max: 1.0,
+        until: Some(30),
+    });
+    config
+}
+
+#[test]
+fn demo_level_configured() {
+    let mut spec: VeilSpec = toml::from_str(VEIL_SPEC).unwrap();
+    let config = spec.levels.remove("string_demo").expect("Missing level");
+    assert!(config.step.min < config.step.max);
+    assert!(!config.escalation.is_empty());
+}
+
+#[test]
+fn escalation_applies_periodically() {
+    let config = escalating_config();
+    let mut info = config.step;
+    for step in 0..10 {
+        config.escalate(step, &mut info);
+    }
+    assert_eq!(info.max, 0.0);
+
+    config.escalate(10, &mut info);
+    assert_eq!(info.min, -1.0);
+    assert_eq!(info.max, 1.0);
+}
+
+#[test]
+fn escalation_stops() {
+    let config = escalating_config();
+    let mut info = config.step;
+    for step in 0..100 {
+        config.escalate(step, &mut info);
+    }
+    assert_eq!(info.max, 3.0);
+}
+
+#[test]
+fn veil_state_from_config() {
+    let mut config = VeilConfig::default();
+    config.zoom = 1;
+    config.forecast_steps = 3;
+    let mut rng = StdRng::from_seed(&[0]);
+    let veil = VeilState::from_config(10, 10, &mut rng, &config);
+    assert!(veil.coord_iter().all(|coord| veil.turns_until(coord).map(|turns| turns <= 3).unwrap_or(true)));
+}
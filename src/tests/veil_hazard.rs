@@ -0,0 +1,165 @@
+use rand::{SeedableRng, StdRng};
+use entity_store::*;
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use observation::shadowcast::{self, ShadowcastEnv};
+use policy::GamePolicy;
+use veil_state::VeilState;
+use veil_config::VeilConfig;
+use veil_hazard::{VeilHazardConfig, VeilHazardRules};
+use tile::{self, TileResolver};
+use tile_buffer::TileBuffer;
+use content::{prototypes, VeilStepInfo, OverlayType, Message, PLAYER_HIT_POINTS};
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+const PLAYER_COORD: Vector2<i32> = Vector2 { x: 5, y: 5 };
+
+// noise is always in this range, so the whole level is veiled
+const ALL_VEIL: VeilStepInfo = VeilStepInfo { x: 0.0, y: 0.0, z: 0.0, min: -10.0, max: 10.0 };
+const NO_VEIL: VeilStepInfo = VeilStepInfo { x: 0.0, y: 0.0, z: 0.0, min: 10.0, max: 11.0 };
+
+const RULES: VeilHazardRules = VeilHazardRules {
+    damage: 3,
+    blurred_vision_distance: Some(2),
+    spawn_probability: 0.0,
+};
+
+struct Env {
+    level: TestLevel,
+    policy: GamePolicy,
+    rng: StdRng,
+    player_id: EntityId,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            policy: GamePolicy::new(),
+            rng: StdRng::from_seed(&[0]),
+            player_id: 0,
+        };
+
+        for y in 0..HEIGHT as i32 {
+            for x in 0..WIDTH as i32 {
+                let id = env.level.allocate();
+                prototypes::stone_floor(&mut env.level.change, id, Vector2::new(x, y));
+            }
+        }
+
+        env.player_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.player_id, PLAYER_COORD);
+        env.level.commit();
+
+        env
+    }
+
+    fn veil_hazards(&mut self, id: EntityId, config: &VeilHazardConfig, info: &VeilStepInfo) -> Option<EntityId> {
+        let mut veil_config = VeilConfig::default();
+        veil_config.step = *info;
+        let veil_state = VeilState::from_config(WIDTH, HEIGHT, &mut self.rng, &veil_config);
+        let spawned = self.policy.veil_hazards(id, config, &mut self.level.change, &self.level.entity_store,
+                                               &self.level.spatial_hash, &veil_state,
+                                               &mut self.level.id_allocator, &mut self.rng);
+        self.level.commit();
+        spawned
+    }
+}
+
+fn player_config(rules: VeilHazardRules) -> VeilHazardConfig {
+    VeilHazardConfig {
+        player: rules,
+        npc: VeilHazardRules::harmless(),
+    }
+}
+
+#[test]
+fn player_damaged_and_blurred_in_veil() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    env.veil_hazards(player_id, &player_config(RULES), &ALL_VEIL);
+
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&(PLAYER_HIT_POINTS - RULES.damage)));
+    assert_eq!(env.level.entity_store.blurred_vision.get(&player_id), Some(&2));
+}
+
+#[test]
+fn death_in_veil_is_reported() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    env.level.change.hit_points.insert(player_id, RULES.damage);
+    env.level.commit();
+
+    env.veil_hazards(player_id, &player_config(RULES), &NO_VEIL);
+    assert_eq!(env.policy.player_death(), None);
+    env.veil_hazards(player_id, &player_config(RULES), &ALL_VEIL);
+
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&0));
+    assert_eq!(env.policy.player_death(), Some(Message::ConsumedByVeil));
+}
+
+#[test]
+fn vision_restored_outside_veil() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    env.veil_hazards(player_id, &player_config(RULES), &ALL_VEIL);
+    env.veil_hazards(player_id, &player_config(RULES), &NO_VEIL);
+
+    assert_eq!(env.level.entity_store.hit_points.get(&player_id), Some(&(PLAYER_HIT_POINTS - RULES.damage)));
+    assert!(env.level.entity_store.blurred_vision.get(&player_id).is_none());
+}
+
+#[test]
+fn veil_creatures_unaffected() {
+    let mut env = Env::new();
+    let id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, id, Vector2::new(1, 1));
+    env.level.commit();
+
+    let config = VeilHazardConfig {
+        player: RULES,
+        npc: RULES,
+    };
+    env.veil_hazards(id, &config, &ALL_VEIL);
+
+    assert!(env.level.entity_store.blurred_vision.get(&id).is_none());
+}
+
+#[test]
+fn veil_creature_spawned_near_player() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    let rules = VeilHazardRules {
+        spawn_probability: 1.0,
+        ..VeilHazardRules::harmless()
+    };
+    let id = env.veil_hazards(player_id, &player_config(rules), &ALL_VEIL).expect("Nothing spawned");
+
+    assert!(env.level.entity_store.npc.contains(&id));
+    let position = *env.level.entity_store.position.get(&id).unwrap();
+    assert!(position != PLAYER_COORD);
+}
+
+#[test]
+fn warning_when_veil_approaches_player() {
+    let mut env = Env::new();
+    let floor_id = env.level.spatial_hash.get(PLAYER_COORD).unwrap().floor_set.iter().next().cloned().unwrap();
+    env.level.change.veil_next.insert(floor_id);
+    env.level.commit();
+
+    let mut knowledge = PlayerKnowledgeGrid::new(WIDTH, HEIGHT);
+    shadowcast::observe(&mut ShadowcastEnv::new(), PLAYER_COORD, &env.level.spatial_hash, 4,
+                        &env.level.entity_store, env.level.time, &mut knowledge);
+
+    let resolver = TileResolver::from_str(include_str!("../res/tiles.toml"));
+    let mut tile_buffer = TileBuffer::new(WIDTH, HEIGHT);
+    tile_buffer.update(Vector2::new(0, 0), &knowledge, &resolver, env.level.time, false);
+
+    let warning = resolver.resolve_overlay(OverlayType::VeilWarning);
+    let next = resolver.resolve_overlay(OverlayType::VeilNext);
+    let player_cell = tile_buffer.get((PLAYER_COORD.x as usize, PLAYER_COORD.y as usize)).unwrap();
+    assert_eq!(player_cell.channels[tile::OVERLAY_CHANNEL], Some(warning));
+    assert!(warning != next);
+}
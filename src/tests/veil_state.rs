@@ -1,10 +1,26 @@
-use rand::{StdRng, SeedableRng};
+use rand::{Rng, StdRng, SeedableRng};
+use entity_store::*;
+use spatial_hash::*;
+use knowledge::PlayerKnowledgeGrid;
+use observation::omniscient;
+use policy::GamePolicy;
+use tile::{self, TileResolver};
+use tile_buffer::TileBuffer;
 use veil_state::*;
-use content::VeilStepInfo;
+use veil_config::{VeilConfig, DEFAULT_FORECAST_STEPS};
+use content::{prototypes, VeilStepInfo, OverlayType};
+use cgmath::Vector2;
 
 const WIDTH: usize = 40;
 const HEIGHT: usize = 40;
 
+fn new_veil_state<R: Rng>(forecast_steps: usize, rng: &mut R, info: &VeilStepInfo) -> VeilState {
+    let mut config = VeilConfig::default();
+    config.forecast_steps = forecast_steps;
+    config.step = *info;
+    VeilState::from_config(WIDTH, HEIGHT, rng, &config)
+}
+
 #[test]
 fn veil_state() {
     let mut rng = StdRng::from_seed(&[0]);
@@ -15,7 +31,7 @@ fn veil_state() {
         min: 0.2,
         max: -0.2,
     };
-    let mut veil = VeilState::new(WIDTH, HEIGHT, &mut rng, &info);
+    let mut veil = new_veil_state(DEFAULT_FORECAST_STEPS, &mut rng, &info);
 
     let to_char = |cell: VeilCell| {
         if cell.current {
@@ -62,3 +78,93 @@ fn veil_state() {
         print!("\n");
     }
 }
+
+const FORECAST_INFO: VeilStepInfo = VeilStepInfo {
+    x: 0.02,
+    y: 0.01,
+    z: 0.02,
+    min: -0.1,
+    max: 0.1,
+};
+const FORECAST_STEPS: usize = 6;
+
+#[test]
+fn forecast_counts_down() {
+    let mut rng = StdRng::from_seed(&[1]);
+    let mut veil = new_veil_state(FORECAST_STEPS, &mut rng, &FORECAST_INFO);
+    let initial: Vec<Option<u32>> = veil.coord_iter().map(|c| veil.turns_until(c)).collect();
+    assert!(initial.iter().any(|t| t.map(|t| t > 1).unwrap_or(false)));
+
+    for steps in 0..FORECAST_STEPS as u32 {
+        for (coord, turns_until) in izip!(veil.coord_iter(), initial.iter()) {
+            if let Some(turns_until) = *turns_until {
+                if turns_until >= steps {
+                    assert_eq!(veil.turns_until(coord), Some(turns_until - steps));
+                }
+            }
+            let cell = veil.get(coord).unwrap();
+            assert_eq!(cell.current, veil.turns_until(coord) == Some(0));
+            assert_eq!(!cell.current && cell.next, veil.turns_until(coord) == Some(1));
+        }
+        veil.step(&mut rng, &FORECAST_INFO);
+    }
+}
+
+#[test]
+fn forecast_length_does_not_change_veil() {
+    let mut short_rng = StdRng::from_seed(&[2]);
+    let mut long_rng = StdRng::from_seed(&[2]);
+    let mut short = new_veil_state(1, &mut short_rng, &FORECAST_INFO);
+    let mut long = new_veil_state(FORECAST_STEPS, &mut long_rng, &FORECAST_INFO);
+
+    for _ in 0..FORECAST_STEPS {
+        for coord in short.coord_iter() {
+            assert_eq!(short.get(coord).unwrap().current, long.get(coord).unwrap().current);
+        }
+        short.step(&mut short_rng, &FORECAST_INFO);
+        long.step(&mut long_rng, &FORECAST_INFO);
+    }
+}
+
+#[test]
+fn forecast_overlay() {
+    let mut rng = StdRng::from_seed(&[1]);
+    let veil = new_veil_state(FORECAST_STEPS, &mut rng, &FORECAST_INFO);
+
+    let mut entity_store = EntityStore::new();
+    let mut change = EntityStoreChange::new();
+    let mut spatial_hash = SpatialHashTable::new(WIDTH, HEIGHT);
+    let mut id = 0;
+    for coord in veil.coord_iter() {
+        prototypes::stone_floor(&mut change, id, coord);
+        id += 1;
+    }
+    spatial_hash.update(&entity_store, &change, 1);
+    entity_store.commit_change(&mut change);
+
+    GamePolicy::new().veil_update(&mut change, &entity_store, &spatial_hash, &veil);
+    spatial_hash.update(&entity_store, &change, 2);
+    entity_store.commit_change(&mut change);
+
+    let mut knowledge = PlayerKnowledgeGrid::new(WIDTH, HEIGHT);
+    omniscient::observe(&spatial_hash, &entity_store, 2, &mut knowledge);
+
+    let resolver = TileResolver::from_str(include_str!("../res/tiles.toml"));
+    let mut tile_buffer = TileBuffer::new(WIDTH, HEIGHT);
+    tile_buffer.update(Vector2::new(0, 0), &knowledge, &resolver, 2, true);
+
+    let mut num_forecast = 0;
+    for (coord, cell) in izip!(tile_buffer.coord_iter(), tile_buffer.iter()) {
+        match veil.turns_until(coord) {
+            Some(0) | None => {
+                assert!(cell.channels[tile::OVERLAY_CHANNEL] != Some(resolver.resolve_overlay(OverlayType::VeilForecast1)));
+            }
+            Some(turns) => {
+                let overlay = OverlayType::veil_forecast(turns).unwrap();
+                assert_eq!(cell.channels[tile::OVERLAY_CHANNEL], Some(resolver.resolve_overlay(overlay)));
+                num_forecast += 1;
+            }
+        }
+    }
+    assert!(num_forecast > 0);
+}
@@ -0,0 +1,113 @@
+use rand::{SeedableRng, StdRng};
+use tests::common::TestLevel;
+use knowledge::PlayerKnowledgeGrid;
+use observation::shadowcast::{self, ShadowcastEnv};
+use behaviour::{movement_cost, FlowField};
+use tile::TileResolver;
+use tile_buffer::TileBuffer;
+use content::{prototypes, TileType, WaterDepth};
+use direction::Direction;
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 3;
+const DISTANCE: u32 = 12;
+const SHALLOW: Vector2<i32> = Vector2 { x: 3, y: 1 };
+const DEEP: Vector2<i32> = Vector2 { x: 5, y: 1 };
+
+struct Env {
+    level: TestLevel,
+    knowledge: PlayerKnowledgeGrid,
+    rng: StdRng,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            knowledge: PlayerKnowledgeGrid::new(WIDTH, HEIGHT),
+            rng: StdRng::from_seed(&[0]),
+        };
+
+        let id = env.level.allocate();
+        prototypes::shallow_water(&mut env.level.change, id, SHALLOW, &mut env.rng);
+        let id = env.level.allocate();
+        prototypes::deep_water(&mut env.level.change, id, DEEP, &mut env.rng);
+        env.level.commit();
+
+        env
+    }
+
+    fn observe(&mut self) {
+        shadowcast::observe(&mut ShadowcastEnv::new(), Vector2::new(0, 1), &self.level.spatial_hash, DISTANCE,
+                            &self.level.entity_store, self.level.time, &mut self.knowledge);
+    }
+}
+
+#[test]
+fn water_is_walkable() {
+    let env = Env::new();
+
+    for &coord in [SHALLOW, DEEP].iter() {
+        assert_eq!(env.level.spatial_hash.get(coord).unwrap().solid_count, 0);
+    }
+    assert_eq!(WaterDepth::of_cell(env.level.spatial_hash.get(SHALLOW).unwrap()), Some(WaterDepth::Shallow));
+    assert_eq!(WaterDepth::of_cell(env.level.spatial_hash.get(DEEP).unwrap()), Some(WaterDepth::Deep));
+    assert_eq!(WaterDepth::of_cell(env.level.spatial_hash.get(Vector2::new(0, 0)).unwrap()), None);
+}
+
+#[test]
+fn deeper_water_costs_more_to_cross() {
+    let mut env = Env::new();
+    env.observe();
+
+    let cost = |coord| movement_cost(env.knowledge.get(coord).unwrap(), None).expect("Impassable");
+    let floor = cost(Vector2::new(0, 0));
+    let shallow = cost(SHALLOW);
+    let deep = cost(DEEP);
+
+    assert!(floor < shallow);
+    assert!(shallow < deep);
+    assert!(WaterDepth::Shallow.turn_cost() < WaterDepth::Deep.turn_cost());
+}
+
+#[test]
+fn flow_field_goes_around_deep_water() {
+    let env = Env::new();
+    let mut flow_field = FlowField::new(WIDTH, HEIGHT);
+
+    // the npc is directly beyond the deep water from the player
+    let player_coord = DEEP - Vector2::new(1, 0);
+    let npc_coord = DEEP + Vector2::new(1, 0);
+    let direction = flow_field.next_direction(npc_coord, player_coord, &env.level.spatial_hash);
+    assert!(direction == Some(Direction::North) || direction == Some(Direction::South));
+
+    // but will wade through shallow water
+    let player_coord = SHALLOW - Vector2::new(1, 0);
+    let npc_coord = SHALLOW + Vector2::new(1, 0);
+    assert_eq!(flow_field.next_direction(npc_coord, player_coord, &env.level.spatial_hash), Some(Direction::West));
+}
+
+#[test]
+fn swimmers_drawn_submerged() {
+    let mut env = Env::new();
+    let id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, id, DEEP);
+    let id = env.level.allocate();
+    prototypes::undead(&mut env.level.change, id, SHALLOW);
+    env.level.commit();
+    env.observe();
+
+    assert_eq!(env.knowledge.get(DEEP).unwrap().water_depth, Some(WaterDepth::Deep));
+
+    let resolver = TileResolver::from_str(include_str!("../res/tiles.toml"));
+    let submerged = resolver.resolve_tile(TileType::Submerged).channels[0].sprite;
+
+    let mut tile_buffer = TileBuffer::new(WIDTH, HEIGHT);
+    tile_buffer.update(Vector2::new(0, 0), &env.knowledge, &resolver, env.level.time, false);
+
+    let deep = tile_buffer.get((DEEP.x as usize, DEEP.y as usize)).unwrap();
+    assert!(deep.channels.iter().any(|c| *c == Some(submerged)));
+    let shallow = tile_buffer.get((SHALLOW.x as usize, SHALLOW.y as usize)).unwrap();
+    assert!(!shallow.channels.iter().any(|c| *c == Some(submerged)));
+}
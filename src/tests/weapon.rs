@@ -0,0 +1,147 @@
+use toml;
+use entity_store::*;
+use tests::common::TestLevel;
+use straight_line::InfiniteAbsoluteLineTraverse;
+use projectile_config::{ProjectileConfig, ProjectileSpec};
+use content::{prototypes, spread, ActionType, Arsenal, Weapon, WeaponType, ProjectileType};
+use cgmath::Vector2;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+const PLAYER_COORD: Vector2<i32> = Vector2 { x: 5, y: 5 };
+
+const PROJECTILE_SPEC: &'static str = include_str!("../res/projectiles.toml");
+
+struct Env {
+    level: TestLevel,
+    player_id: EntityId,
+    config: ProjectileConfig,
+}
+
+impl Env {
+    fn new() -> Self {
+        let spec: ProjectileSpec = toml::from_str(PROJECTILE_SPEC).unwrap();
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            player_id: 0,
+            config: ProjectileConfig::from_descs(spec.projectile),
+        };
+
+        env.player_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.player_id, PLAYER_COORD);
+        env.level.commit();
+
+        env
+    }
+
+    fn act(&mut self, action: ActionType) {
+        action.populate(&mut self.level.change, &self.level.entity_store, &mut self.level.id_allocator);
+        self.level.commit();
+    }
+
+    fn equip(&mut self, weapon: Weapon) {
+        self.level.change.weapons.insert(self.player_id, Arsenal::new(vec![weapon]));
+        self.level.commit();
+    }
+
+    fn fire(&mut self) {
+        let weapon_type = self.weapon().weapon_type;
+        let projectile = self.config.projectile(weapon_type.projectile_type());
+        let traverse = InfiniteAbsoluteLineTraverse::new_offset(PLAYER_COORD, Vector2::new(1, 0));
+        let action = ActionType::FireWeapon(self.player_id, projectile, traverse);
+        self.act(action);
+    }
+
+    fn weapon(&self) -> Weapon {
+        *self.level.entity_store.weapons.get(&self.player_id).unwrap().equipped().unwrap()
+    }
+}
+
+#[test]
+fn firing_uses_ammo() {
+    let mut env = Env::new();
+    env.equip(Weapon::new(WeaponType::Pistol, 0));
+    env.fire();
+
+    assert_eq!(env.weapon().loaded, WeaponType::Pistol.capacity() - 1);
+    assert_eq!(env.level.entity_store.projectile.len(), 1);
+}
+
+#[test]
+fn empty_weapon_fires_nothing() {
+    let mut env = Env::new();
+    env.equip(Weapon { loaded: 0, ..Weapon::new(WeaponType::Pistol, 0) });
+    env.fire();
+
+    assert!(env.level.entity_store.projectile.is_empty());
+    assert!(env.level.entity_store.muzzle_flash.is_empty());
+}
+
+#[test]
+fn shotgun_fires_spread() {
+    let mut env = Env::new();
+    env.equip(Weapon::new(WeaponType::Shotgun, 0));
+    env.fire();
+
+    assert_eq!(env.level.entity_store.projectile.len(), WeaponType::Shotgun.pellets());
+    assert_eq!(env.level.entity_store.muzzle_flash.len(), 1);
+    for projectile in env.level.entity_store.projectile.values() {
+        assert_eq!(projectile.range, env.config.projectile(ProjectileType::Pellet).range);
+    }
+}
+
+#[test]
+fn spread_fans_around_aim() {
+    let traverse = InfiniteAbsoluteLineTraverse::new_offset(PLAYER_COORD, Vector2::new(1, 0));
+    let lines = spread(traverse, 5);
+    let deltas: Vec<_> = lines.iter().map(|t| t.delta()).collect();
+
+    assert!(lines.iter().all(|t| t.current() == PLAYER_COORD));
+    assert_eq!(deltas[2].y, 0);
+    assert!(deltas[2].x > 0);
+    for i in 0..2 {
+        assert_eq!(deltas[i].x, deltas[4 - i].x);
+        assert_eq!(deltas[i].y, -deltas[4 - i].y);
+        assert!(deltas[i].y < deltas[i + 1].y);
+    }
+
+    assert_eq!(spread(traverse, 1)[0].delta(), traverse.delta());
+}
+
+#[test]
+fn rifle_pierces_and_delays_turn() {
+    let mut env = Env::new();
+    env.equip(Weapon::new(WeaponType::Rifle, 0));
+    env.fire();
+
+    let projectile = *env.level.entity_store.projectile.values().next().unwrap();
+    assert!(projectile.penetration > 0);
+    assert_eq!(env.level.entity_store.turn_delay.get(&env.player_id), Some(&WeaponType::Rifle.fire_time()));
+}
+
+#[test]
+fn reload_fills_from_reserve() {
+    let mut env = Env::new();
+    env.equip(Weapon { loaded: 1, ..Weapon::new(WeaponType::Pistol, 3) });
+    let player_id = env.player_id;
+    env.act(ActionType::Reload(player_id));
+
+    assert_eq!(env.weapon().loaded, 4);
+    assert_eq!(env.weapon().reserve, 0);
+    assert!(!env.weapon().can_reload());
+    assert_eq!(env.level.entity_store.turn_delay.get(&player_id), Some(&WeaponType::Pistol.reload_time()));
+}
+
+#[test]
+fn cycle_weapons() {
+    let mut env = Env::new();
+    let player_id = env.player_id;
+    assert_eq!(env.weapon().weapon_type, WeaponType::Pistol);
+
+    env.act(ActionType::CycleWeapon(player_id));
+    assert_eq!(env.weapon().weapon_type, WeaponType::Shotgun);
+
+    env.act(ActionType::CycleWeapon(player_id));
+    env.act(ActionType::CycleWeapon(player_id));
+    assert_eq!(env.weapon().weapon_type, WeaponType::Pistol);
+}
@@ -0,0 +1,125 @@
+use rand::{SeedableRng, StdRng};
+use entity_store::*;
+use tests::common::TestLevel;
+use policy::GamePolicy;
+use weather::Weather;
+use content::{prototypes, MAX_RAIN_DENSITY, MAX_WIND, RAIN_LENGTH, HEAVY_RAIN_VISION_DISTANCE,
+              WEATHER_INTENSITY_STEP};
+use cgmath::Vector2;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 20;
+const OUTSIDE: Vector2<i32> = Vector2 { x: 2, y: 2 };
+const INSIDE: Vector2<i32> = Vector2 { x: 15, y: 15 };
+const HEAVY_RAIN: Vector2<i32> = Vector2 { x: 5, y: 5 };
+
+struct Env {
+    level: TestLevel,
+    policy: GamePolicy,
+    rng: StdRng,
+    outside_id: EntityId,
+    inside_id: EntityId,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut env = Env {
+            level: TestLevel::new(WIDTH, HEIGHT),
+            policy: GamePolicy::new(),
+            rng: StdRng::from_seed(&[0]),
+            outside_id: 0,
+            inside_id: 0,
+        };
+
+        let floor_id = env.level.allocate();
+        prototypes::stone_floor(&mut env.level.change, floor_id, INSIDE);
+        env.level.change.inside.insert(floor_id);
+
+        env.outside_id = env.level.allocate();
+        prototypes::player(&mut env.level.change, env.outside_id, OUTSIDE);
+        env.inside_id = env.level.allocate();
+        prototypes::undead(&mut env.level.change, env.inside_id, INSIDE);
+        env.level.commit();
+
+        env
+    }
+
+    fn update(&mut self, weather: &Weather, turns: usize) {
+        for _ in 0..turns {
+            self.policy.weather_update(weather, &mut self.level.change, &self.level.entity_store,
+                                       &self.level.spatial_hash, &mut self.level.id_allocator, &mut self.rng);
+            self.level.commit();
+        }
+    }
+}
+
+#[test]
+fn rain_follows_intensity() {
+    let mut env = Env::new();
+    let target = (MAX_RAIN_DENSITY * (WIDTH * HEIGHT) as f64) as usize;
+
+    env.update(&Weather::new(1.0, 0.0), 10);
+    assert_eq!(env.level.entity_store.rain.len(), target);
+
+    env.update(&Weather::new(0.0, 0.0), 10);
+    assert!(env.level.entity_store.rain.is_empty());
+}
+
+#[test]
+fn wind_tilts_rain() {
+    let weather = Weather::new(0.5, MAX_WIND);
+    assert_eq!(weather.rain_delta(), Vector2::new((MAX_WIND * RAIN_LENGTH as f64) as i32, RAIN_LENGTH));
+    assert_eq!(Weather::new(0.5, 0.0).rain_delta(), Vector2::new(0, RAIN_LENGTH));
+}
+
+#[test]
+fn heavy_rain_limits_vision_outside() {
+    let mut env = Env::new();
+
+    env.update(&Weather::new(1.0, 0.0), 1);
+    assert_eq!(env.level.entity_store.weather_vision.get(&env.outside_id), Some(&HEAVY_RAIN_VISION_DISTANCE));
+    assert!(env.level.entity_store.weather_vision.get(&env.inside_id).is_none());
+
+    env.update(&Weather::new(0.1, 0.0), 1);
+    assert!(env.level.entity_store.weather_vision.get(&env.outside_id).is_none());
+}
+
+#[test]
+fn heavy_rain_opacity_only_changes_with_intensity() {
+    let mut env = Env::new();
+    let id = env.level.allocate();
+    prototypes::heavy_rain(&mut env.level.change, id, HEAVY_RAIN);
+    env.level.commit();
+
+    let weather = Weather::new(0.5, 0.0);
+    env.update(&weather, 1);
+    assert_eq!(env.level.entity_store.opacity.get(&id), Some(&weather.heavy_rain_opacity()));
+
+    env.policy.weather_update(&weather, &mut env.level.change, &env.level.entity_store, &env.level.spatial_hash,
+                              &mut env.level.id_allocator, &mut env.rng);
+    assert!(env.level.change.opacity.is_empty());
+    env.level.commit();
+
+    // the intensity drifts a little every turn, which shouldn't be noticed
+    let drifted = Weather::new(0.5 + WEATHER_INTENSITY_STEP, 0.0);
+    env.policy.weather_update(&drifted, &mut env.level.change, &env.level.entity_store, &env.level.spatial_hash,
+                              &mut env.level.id_allocator, &mut env.rng);
+    assert!(env.level.change.opacity.is_empty());
+    env.level.commit();
+
+    let heavier = Weather::new(0.9, 0.0);
+    env.update(&heavier, 1);
+    assert!(heavier.heavy_rain_opacity() > weather.heavy_rain_opacity());
+    assert_eq!(env.level.entity_store.opacity.get(&id), Some(&heavier.heavy_rain_opacity()));
+}
+
+#[test]
+fn weather_stays_in_range() {
+    let mut rng = StdRng::from_seed(&[1]);
+    let mut weather = Weather::new(0.5, 0.0);
+    for _ in 0..1000 {
+        weather.step(&mut rng);
+        assert!(weather.intensity >= 0.0 && weather.intensity <= 1.0);
+        assert!(weather.wind.abs() <= MAX_WIND);
+    }
+}
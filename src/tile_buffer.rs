@@ -1,9 +1,10 @@
 use cgmath::Vector2;
 
 use grid::{StaticGrid, StaticGridIdx, static_grid};
-use content::{ComplexTile, OverlayType};
+use content::{ComplexTile, OverlayType, TileType, WaterDepth, LightColour, MEMORY_FADE_TIME,
+              GHOST_TILE_PRIORITY, GHOST_TILES, GHOST_MAX_AGE};
 use tile;
-use knowledge::{PlayerKnowledgeGrid, PlayerKnowledgeTile};
+use knowledge::{PlayerKnowledgeGrid, PlayerKnowledgeTile, EnemyGhost};
 
 const TILE_FRONT_PRIORITY: u8 = 255;
 
@@ -11,6 +12,9 @@ const TILE_FRONT_PRIORITY: u8 = 255;
 pub struct TileBufferCell {
     pub channels: [Option<tile::TileCoord>; tile::NUM_TILE_CHANNELS],
     pub visible: bool,
+    pub light: LightColour,
+    // 0 for cells seen this turn, rising to 1 as memories of the cell age
+    pub staleness: f64,
     priorities: [u8; tile::NUM_TILE_CHANNELS],
 }
 
@@ -24,6 +28,8 @@ impl Default for TileBufferCell {
         TileBufferCell {
             channels: [None; tile::NUM_TILE_CHANNELS],
             visible: true,
+            light: LightColour::white(),
+            staleness: 0.0,
             priorities: [0; tile::NUM_TILE_CHANNELS],
         }
     }
@@ -33,6 +39,8 @@ impl TileBufferCell {
     fn clear(&mut self) {
         self.channels = [None; tile::NUM_TILE_CHANNELS];
         self.visible = false;
+        self.light = LightColour::black();
+        self.staleness = 0.0;
         self.priorities = [0; tile::NUM_TILE_CHANNELS];
     }
 
@@ -46,6 +54,11 @@ impl TileBufferCell {
     }
 }
 
+fn ghost_tile_type(ghost: &EnemyGhost, time: u64) -> TileType {
+    let stage = ghost.age(time) * GHOST_TILES.len() as u64 / GHOST_MAX_AGE;
+    GHOST_TILES[(stage as usize).min(GHOST_TILES.len() - 1)]
+}
+
 pub type Iter<'a> = static_grid::Iter<'a, TileBufferCell>;
 pub type CoordIter = static_grid::CoordIter;
 
@@ -78,7 +91,8 @@ impl TileBuffer {
 
     pub fn update(&mut self, offset: Vector2<i32>,
                   knowledge: &PlayerKnowledgeGrid,
-                  resolver: &tile::TileResolver, time: u64) {
+                  resolver: &tile::TileResolver, time: u64,
+                  veil_forecast: bool) {
 
         for (coord, mut cell) in izip!(self.grid.coord_iter(), self.grid.iter_mut()) {
             cell.clear();
@@ -86,11 +100,24 @@ impl TileBuffer {
 
             if let Some(knowledge_cell) = knowledge.get(knowledge_coord) {
                 cell.visible = knowledge_cell.last_updated == time;
+                let age = time.saturating_sub(knowledge_cell.last_updated);
+                cell.staleness = (age as f64 / MEMORY_FADE_TIME as f64).min(1.0);
                 if cell.visible {
-                    if knowledge_cell.veil_cell.current && knowledge_cell.veil_cell.next {
+                    cell.light = knowledge_cell.light * knowledge_cell.visibility;
+                    let forecast_overlay = if veil_forecast {
+                        knowledge_cell.veil_forecast.and_then(OverlayType::veil_forecast)
+                    } else {
+                        None
+                    };
+                    if knowledge_cell.player && knowledge_cell.veil_cell.next && !knowledge_cell.veil_cell.current {
+                        // the veil is about to cover the player
+                        cell.channels[tile::OVERLAY_CHANNEL] = Some(resolver.resolve_overlay(OverlayType::VeilWarning));
+                    } else if knowledge_cell.veil_cell.current && knowledge_cell.veil_cell.next {
                         cell.channels[tile::OVERLAY_CHANNEL] = Some(resolver.resolve_overlay(OverlayType::Veil));
                     } else if knowledge_cell.veil_cell.current {
                         cell.channels[tile::OVERLAY_CHANNEL] = Some(resolver.resolve_overlay(OverlayType::VeilCurrent));
+                    } else if let Some(overlay) = forecast_overlay {
+                        cell.channels[tile::OVERLAY_CHANNEL] = Some(resolver.resolve_overlay(overlay));
                     } else if knowledge_cell.veil_cell.next {
                         cell.channels[tile::OVERLAY_CHANNEL] = Some(resolver.resolve_overlay(OverlayType::VeilNext));
                     } else {
@@ -128,6 +155,21 @@ impl TileBuffer {
                     if let Some(front) = knowledge.get(north_coord).and_then(|c| c.tile_front) {
                         cell.update(resolver.resolve_tile(front), TILE_FRONT_PRIORITY);
                     }
+                    // creatures swimming in deep water are mostly hidden by it
+                    if knowledge_cell.water_depth == Some(WaterDepth::Deep) &&
+                        (knowledge_cell.player || knowledge_cell.enemy.is_some()) {
+                        cell.update(resolver.resolve_tile(TileType::Submerged), TILE_FRONT_PRIORITY);
+                    }
+                }
+            }
+        }
+
+        // mark the last known positions of enemies which are out of sight,
+        // fading as the sightings get older
+        for (_, ghost) in knowledge.ghosts() {
+            if let Some(cell) = self.grid.get_mut(ghost.coord - offset) {
+                if !cell.visible {
+                    cell.update(resolver.resolve_tile(ghost_tile_type(ghost, time)), GHOST_TILE_PRIORITY);
                 }
             }
         }
@@ -7,9 +7,13 @@ use behaviour::*;
 use entity_store::*;
 use spatial_hash::*;
 use entity_id_allocator::*;
-use observation::shadowcast::ShadowcastEnv;
+use observation::FovEnv;
+use observation::FovCacheTable;
+use light_grid::LightGrid;
+use weather::Weather;
 use meta_action::*;
 use policy::*;
+use projectile_config::ProjectileConfig;
 use commit::{self, CommitEnv};
 use renderer::GameRenderer;
 use input::GameInput;
@@ -17,6 +21,8 @@ use schedule::{Schedule, ScheduleEntry};
 use content::ActionType;
 use player_act;
 use npc_act;
+use grid_search;
+use alert;
 
 #[derive(Debug)]
 pub enum Error {
@@ -24,6 +30,7 @@ pub enum Error {
     MissingNpcBehaviour,
     PlayerTurnError(player_act::Error),
     NpcTurnError(npc_act::Error),
+    AlertFailed(grid_search::Error),
     CommitFailed(commit::Error),
 }
 pub type Result<T> = result::Result<T, Error>;
@@ -40,6 +47,12 @@ impl From<commit::Error> for Error {
     }
 }
 
+impl From<grid_search::Error> for Error {
+    fn from(e: grid_search::Error) -> Self {
+        Error::AlertFailed(e)
+    }
+}
+
 impl From<npc_act::Error> for Error {
     fn from(e: npc_act::Error) -> Self {
         Error::NpcTurnError(e)
@@ -63,14 +76,19 @@ pub struct TurnEnv<'a, R: 'a + Rng, Ren: 'a + GameRenderer, Inp: 'a + GameInput>
     pub id_allocator: &'a mut EntityIdAllocator,
     pub spatial_hash: &'a mut SpatialHashTable,
     pub behaviour_env: &'a mut BehaviourEnv,
+    pub behaviour_config: &'a BehaviourConfig,
     pub player_id: EntityId,
     pub entity_id: EntityId,
     pub player_knowledge: &'a mut PlayerKnowledgeGrid,
     pub knowledge: &'a mut HashMap<EntityId, PlayerKnowledgeGrid>,
     pub behaviour: &'a mut HashMap<EntityId, BehaviourState>,
-    pub shadowcast: &'a mut ShadowcastEnv,
+    pub fov: &'a mut FovEnv,
+    pub fov_cache: &'a mut FovCacheTable,
+    pub light_grid: &'a mut LightGrid,
+    pub weather: &'a Weather,
     pub time: &'a mut u64,
     pub policy: &'a mut GamePolicy,
+    pub projectile_config: &'a ProjectileConfig,
     pub rng: &'a mut R,
     pub schedule: &'a mut Schedule<ActionType>,
 }
@@ -87,9 +105,12 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> TurnEnv<'a, R, Ren, Inp> {
                 spatial_hash: self.spatial_hash,
                 entity_id: self.entity_id,
                 knowledge: self.player_knowledge,
-                shadowcast: self.shadowcast,
+                fov: self.fov,
+                fov_cache: self.fov_cache,
+                light_grid: self.light_grid,
                 time: self.time,
                 policy: self.policy,
+                projectile_config: self.projectile_config,
                 rng: self.rng,
             }.act()?;
 
@@ -99,16 +120,25 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> TurnEnv<'a, R, Ren, Inp> {
                 MetaAction::Debug(debug_action) => return Ok(TurnResolution::Debug(debug_action)),
             }
         } else if self.entity_store.npc.contains(&self.entity_id) {
-            npc_act::NpcActEnv {
+            let action = npc_act::NpcActEnv {
                 entity_store: self.entity_store,
                 spatial_hash: self.spatial_hash,
                 entity_id: self.entity_id,
                 knowledge: self.knowledge.get_mut(&self.entity_id).ok_or(Error::MissingNpcKnowledge)?,
                 behaviour_state: self.behaviour.get_mut(&self.entity_id).ok_or(Error::MissingNpcBehaviour)?,
                 behaviour_env: self.behaviour_env,
-                shadowcast: self.shadowcast,
+                behaviour_config: self.behaviour_config,
+                fov: self.fov,
+                fov_cache: self.fov_cache,
+                light_grid: self.light_grid,
                 time: self.time,
-            }.act()?
+                rng: self.rng,
+            }.act()?;
+
+            alert::broadcast(self.entity_id, self.entity_store, self.spatial_hash,
+                             &mut self.behaviour_env.search_env, self.knowledge, *self.time)?;
+
+            action
         } else {
             return Ok(TurnResolution::NoEntity);
         };
@@ -120,7 +150,12 @@ impl<'a, R: Rng, Ren: GameRenderer, Inp: GameInput> TurnEnv<'a, R, Ren, Inp> {
             spatial_hash: self.spatial_hash,
             player_knowledge: self.player_knowledge,
             player_id: self.player_id,
-            shadowcast: self.shadowcast,
+            knowledge: self.knowledge,
+            behaviour_env: self.behaviour_env,
+            fov: self.fov,
+            fov_cache: self.fov_cache,
+            light_grid: self.light_grid,
+            weather: self.weather,
             time: self.time,
             reactions: self.reactions,
             action_schedule_entries: self.action_schedule_entries,
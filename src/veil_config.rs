@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use content::VeilStepInfo;
+use perlin::FractalConfig;
+use resources::{self, VEIL_SPEC};
+use simple_file;
+
+pub const DEFAULT_ZOOM: usize = 20;
+pub const DEFAULT_FORECAST_STEPS: usize = 8;
+
+fn default_zoom() -> usize { DEFAULT_ZOOM }
+fn default_forecast_steps() -> usize { DEFAULT_FORECAST_STEPS }
+
+// A change made to the veil's step info at regular intervals.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VeilEscalation {
+    pub every: u64,
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+    #[serde(default)]
+    pub z: f64,
+    #[serde(default)]
+    pub min: f64,
+    #[serde(default)]
+    pub max: f64,
+    #[serde(default)]
+    pub until: Option<u64>,
+}
+
+impl VeilEscalation {
+    fn applies(&self, step: u64) -> bool {
+        self.every > 0 && step > 0 && step % self.every == 0 &&
+            self.until.map(|until| step <= until).unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VeilConfig {
+    #[serde(default = "default_zoom")]
+    pub zoom: usize,
+    #[serde(default = "default_forecast_steps")]
+    pub forecast_steps: usize,
+    #[serde(default)]
+    pub noise: FractalConfig,
+    pub step: VeilStepInfo,
+    #[serde(default)]
+    pub escalation: Vec<VeilEscalation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VeilSpec {
+    pub levels: HashMap<String, VeilConfig>,
+}
+
+impl Default for VeilConfig {
+    fn default() -> Self {
+        VeilConfig {
+            zoom: DEFAULT_ZOOM,
+            forecast_steps: DEFAULT_FORECAST_STEPS,
+            noise: Default::default(),
+            step: VeilStepInfo {
+                x: 0.02,
+                y: 0.01,
+                z: 0.02,
+                min: -0.02,
+                max: 0.02,
+            },
+            escalation: Vec::new(),
+        }
+    }
+}
+
+impl VeilConfig {
+    pub fn load(level: &str) -> Self {
+        let mut spec = simple_file::read_toml::<_, VeilSpec>(resources::res_path(VEIL_SPEC))
+            .expect("Failed to read veil spec");
+        spec.levels.remove(level).expect("No veil config for level")
+    }
+
+    // Applies any escalations due on the given veil step.
+    pub fn escalate(&self, step: u64, info: &mut VeilStepInfo) {
+        for escalation in self.escalation.iter() {
+            if escalation.applies(step) {
+                info.x += escalation.x;
+                info.y += escalation.y;
+                info.z += escalation.z;
+                info.min += escalation.min;
+                info.max += escalation.max;
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use resources::{self, VEIL_HAZARD_SPEC};
+use simple_file;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VeilHazardRules {
+    pub damage: u32,
+    pub blurred_vision_distance: Option<u32>,
+    pub spawn_probability: f64,
+}
+
+impl VeilHazardRules {
+    pub fn harmless() -> Self {
+        VeilHazardRules {
+            damage: 0,
+            blurred_vision_distance: None,
+            spawn_probability: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VeilHazardConfig {
+    pub player: VeilHazardRules,
+    pub npc: VeilHazardRules,
+}
+
+impl Default for VeilHazardConfig {
+    fn default() -> Self {
+        VeilHazardConfig {
+            player: VeilHazardRules::harmless(),
+            npc: VeilHazardRules::harmless(),
+        }
+    }
+}
+
+impl VeilHazardConfig {
+    pub fn load() -> Self {
+        simple_file::read_toml(resources::res_path(VEIL_HAZARD_SPEC))
+            .expect("Failed to read veil hazard spec")
+    }
+}
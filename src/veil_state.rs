@@ -1,13 +1,11 @@
-use std::mem;
 use std::slice;
+use std::collections::VecDeque;
 use rand::Rng;
 use grid::{StaticGrid, static_grid};
-use perlin::{PerlinGrid, PerlinWrapType};
+use perlin::{FractalNoise, PerlinWrapType};
 use cgmath::Vector2;
 use content::VeilStepInfo;
-
-const ZOOM: usize = 20;
-const ZOOM_F: f64 = ZOOM as f64;
+use veil_config::VeilConfig;
 
 pub type CoordIter = static_grid::CoordIter;
 pub struct Iter<'a> {
@@ -89,43 +87,76 @@ impl Default for VeilCell {
 }
 
 pub struct VeilState {
-    current: StaticGrid<bool>,
-    next: StaticGrid<bool>,
-    perlin: PerlinGrid,
+    // steps[0] is the current step, and steps[n] is n steps in the future
+    steps: VecDeque<StaticGrid<bool>>,
+    noise: FractalNoise,
     dx: f64,
     dy: f64,
+    num_steps: u64,
 }
 
 impl VeilState {
-    pub fn new<R: Rng>(width: usize, height: usize, rng: &mut R, info: &VeilStepInfo) -> Self {
+    pub fn from_config<R: Rng>(width: usize, height: usize, rng: &mut R, config: &VeilConfig) -> Self {
+        let zoom = config.zoom;
+        let forecast_steps = config.forecast_steps;
+        let info = &config.step;
 
-        let mut perlin = PerlinGrid::new((width - 1) / ZOOM + 1,
-                                         (height - 1) / ZOOM + 1,
-                                         PerlinWrapType::Regenerate,
-                                         rng);
+        assert!(zoom > 0, "The veil zoom must be positive");
+        assert!(forecast_steps > 0, "The veil must be forecast at least one step ahead");
 
-        let mut current = StaticGrid::new_default(width, height);
-        let mut next = StaticGrid::new_default(width, height);
+        let noise = FractalNoise::new((width - 1) / zoom + 1,
+                                      (height - 1) / zoom + 1,
+                                      PerlinWrapType::Regenerate,
+                                      &config.noise,
+                                      rng);
 
-        let dx = 1.0 / ZOOM_F;
-        let dy = 1.0 / ZOOM_F;
+        let dx = 1.0 / zoom as f64;
+        let dy = 1.0 / zoom as f64;
+
+        let mut current = StaticGrid::new_default(width, height);
+        record(info, dx, dy, &noise, &mut current);
 
-        record(info, dx, dy, &perlin, &mut current);
-        mutate(info, &mut perlin, rng);
-        record(info, dx, dy, &perlin, &mut next);
+        let mut steps = VecDeque::with_capacity(forecast_steps + 1);
+        steps.push_back(current);
 
-        VeilState {
-            current: current,
-            next: next,
-            perlin: perlin,
+        let mut veil_state = VeilState {
+            steps: steps,
+            noise: noise,
             dx: dx,
             dy: dy,
+            num_steps: 0,
+        };
+
+        for _ in 0..forecast_steps {
+            let grid = StaticGrid::new_default(width, height);
+            veil_state.push_step(grid, rng, info);
         }
+
+        veil_state
+    }
+
+    fn current(&self) -> &StaticGrid<bool> {
+        &self.steps[0]
+    }
+
+    fn next(&self) -> &StaticGrid<bool> {
+        &self.steps[1]
+    }
+
+    fn push_step<R: Rng>(&mut self, mut grid: StaticGrid<bool>, rng: &mut R, info: &VeilStepInfo) {
+        mutate(info, &mut self.noise, rng);
+        record(info, self.dx, self.dy, &self.noise, &mut grid);
+        self.steps.push_back(grid);
+    }
+
+    // Returns the number of times the veil has stepped since it was created.
+    pub fn num_steps(&self) -> u64 {
+        self.num_steps
     }
 
     pub fn get(&self, coord: Vector2<i32>) -> Option<VeilCell> {
-        self.current.get(coord).and_then(|current| {
-            self.next.get(coord).map(|next| {
+        self.current().get(coord).and_then(|current| {
+            self.next().get(coord).map(|next| {
                 VeilCell {
                     current: *current,
                     next: *next,
@@ -134,28 +165,39 @@ impl VeilState {
         })
     }
 
+    // Returns the number of steps until the veil covers a cell, which is 0
+    // if it is currently covered, or None if it isn't covered in any
+    // forecast step.
+    pub fn turns_until(&self, coord: Vector2<i32>) -> Option<u32> {
+        self.steps.iter()
+            .position(|grid| grid.get(coord).cloned().unwrap_or(false))
+            .map(|steps| steps as u32)
+    }
+
     pub fn rows(&self) -> RowIter {
         RowIter {
-            current: self.current.rows(),
-            next: self.next.rows(),
+            current: self.current().rows(),
+            next: self.next().rows(),
         }
     }
 
     pub fn iter(&self) -> Iter {
         Iter {
-            current: self.current.iter(),
-            next: self.next.iter(),
+            current: self.current().iter(),
+            next: self.next().iter(),
         }
     }
 
     pub fn coord_iter(&self) -> CoordIter {
-        self.current.coord_iter()
+        self.current().coord_iter()
     }
 
+    // Steps are generated as they enter the forecast, so changes to the
+    // step info only become visible once the existing forecast has passed.
     pub fn step<R: Rng>(&mut self, rng: &mut R, info: &VeilStepInfo) {
-        mem::swap(&mut self.current, &mut self.next);
-        mutate(info, &mut self.perlin, rng);
-        record(info, self.dx, self.dy, &self.perlin, &mut self.next);
+        let grid = self.steps.pop_front().expect("Missing current veil step");
+        self.push_step(grid, rng, info);
+        self.num_steps += 1;
     }
 }
 
@@ -167,17 +209,17 @@ fn is_veil(info: &VeilStepInfo, noise: f64) -> bool {
     }
 }
 
-fn mutate<R: Rng>(info: &VeilStepInfo, perlin: &mut PerlinGrid, rng: &mut R) {
-    perlin.scroll(rng, info.x, info.y);
-    perlin.mutate(rng, info.z);
+fn mutate<R: Rng>(info: &VeilStepInfo, noise: &mut FractalNoise, rng: &mut R) {
+    noise.scroll(rng, info.x, info.y);
+    noise.mutate(rng, info.z);
 }
 
-fn record(info: &VeilStepInfo, dx: f64, dy: f64, perlin: &PerlinGrid, grid: &mut StaticGrid<bool>) {
+fn record(info: &VeilStepInfo, dx: f64, dy: f64, noise: &FractalNoise, grid: &mut StaticGrid<bool>) {
     for (coord, cell) in izip!(grid.coord_iter(), grid.iter_mut()) {
         let x = dx * coord.x as f64;
         let y = dy * coord.y as f64;
 
-        if let Some(noise) = perlin.noise(x, y) {
+        if let Some(noise) = noise.noise(x, y) {
             *cell = is_veil(info, noise);
         }
     }
@@ -0,0 +1,82 @@
+use rand::Rng;
+use cgmath::Vector2;
+use content::{RAIN_LENGTH, MAX_RAIN_DENSITY, WEATHER_INTENSITY_STEP, WIND_STEP, MAX_WIND,
+              HEAVY_RAIN_INTENSITY, HEAVY_RAIN_VISION_DISTANCE, MAX_RAIN_NOISE_MUFFLING,
+              HEAVY_RAIN_MIN_OPACITY, HEAVY_RAIN_MAX_OPACITY, HEAVY_RAIN_OPACITY_STEPS};
+
+// moves value towards target by at most step
+fn approach(value: f64, target: f64, step: f64) -> f64 {
+    if value < target {
+        (value + step).min(target)
+    } else {
+        (value - step).max(target)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Weather {
+    // from 0 (no rain) to 1 (downpour)
+    pub intensity: f64,
+    // horizontal cells rain is blown for each cell it falls
+    pub wind: f64,
+    target_intensity: f64,
+    target_wind: f64,
+}
+
+impl Weather {
+    pub fn new(intensity: f64, wind: f64) -> Self {
+        Weather {
+            intensity: intensity,
+            wind: wind,
+            target_intensity: intensity,
+            target_wind: wind,
+        }
+    }
+
+    // Moves the weather towards its current target, choosing a new
+    // target once it's reached.
+    pub fn step<R: Rng>(&mut self, rng: &mut R) {
+        self.intensity = approach(self.intensity, self.target_intensity, WEATHER_INTENSITY_STEP);
+        if self.intensity == self.target_intensity {
+            self.target_intensity = rng.next_f64();
+        }
+
+        self.wind = approach(self.wind, self.target_wind, WIND_STEP);
+        if self.wind == self.target_wind {
+            self.target_wind = (rng.next_f64() * 2.0 - 1.0) * MAX_WIND;
+        }
+    }
+
+    pub fn rain_density(&self) -> f64 {
+        self.intensity * MAX_RAIN_DENSITY
+    }
+
+    // offset from the start to the end of a rain drop's fall
+    pub fn rain_delta(&self) -> Vector2<i32> {
+        Vector2::new((self.wind * RAIN_LENGTH as f64).round() as i32, RAIN_LENGTH)
+    }
+
+    pub fn is_heavy(&self) -> bool {
+        self.intensity >= HEAVY_RAIN_INTENSITY
+    }
+
+    // limit on the vision distance of entities out in the rain
+    pub fn vision_distance(&self) -> Option<u32> {
+        if self.is_heavy() {
+            Some(HEAVY_RAIN_VISION_DISTANCE)
+        } else {
+            None
+        }
+    }
+
+    // opacity of patches of heavy rain, which thicken as the rain gets heavier
+    pub fn heavy_rain_opacity(&self) -> f64 {
+        let steps = HEAVY_RAIN_OPACITY_STEPS as f64;
+        let thickness = (self.intensity * steps).round() / steps;
+        HEAVY_RAIN_MIN_OPACITY + thickness * (HEAVY_RAIN_MAX_OPACITY - HEAVY_RAIN_MIN_OPACITY)
+    }
+
+    pub fn noise_muffling(&self) -> u32 {
+        (self.intensity * MAX_RAIN_NOISE_MUFFLING as f64).round() as u32
+    }
+}